@@ -0,0 +1,329 @@
+//! The syntax tree built by the parser and consumed by semantic analysis.
+
+use crate::token::Span;
+
+/// A whole .lol file, everything between `#HAI` and `#KTHXBYE`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Document {
+    /// Comments that come before the head, they end up right after `<html>`.
+    pub comments: Vec<String>,
+    /// The head, if there is one.
+    pub head: Option<Head>,
+    /// Everything after the head.
+    pub body: Vec<Node>,
+}
+
+/// The `#MAEK HEAD ... #OIC` block, everything in it in the order it was written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Head {
+    /// The contents of the head.
+    pub items: Vec<HeadItem>,
+}
+
+/// Anything that can show up in the head.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HeadItem {
+    /// `#GIMMEH TITLE ... #MKAY`, only one is allowed (semantic analysis reports any after the first).
+    Title(Setting),
+    /// `#GIMMEH DESCRIPSHUN ... #MKAY` and the other tags that turn into a `<meta name>`.
+    Meta(Meta),
+    /// `#GIMMEH STYLESHEET url #MKAY`
+    Stylesheet(Media),
+    /// `#MAEK STYLE` up to `#KTHX STYLE`
+    Style(StyleBlock),
+    /// `#GIMMEH SCRIPT url #MKAY`
+    Script(Media),
+    /// `#GIMMEH ICON url #MKAY`
+    Icon(Media),
+}
+
+/// Which `<meta name>` a head tag turns into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetaName {
+    /// `#GIMMEH DESCRIPSHUN`
+    Description,
+    /// `#GIMMEH AUTHOR`
+    Author,
+    /// `#GIMMEH KEYWORDZ`
+    Keywords,
+    /// `#GIMMEH VIEWPORT`
+    Viewport,
+}
+
+/// A `<meta name content>` tag, the content is trimmed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Meta {
+    /// The name attribute.
+    pub name: MetaName,
+    /// The content attribute.
+    pub content: String,
+}
+
+/// Css for the page, kept exactly as written like a code block.
+/// The span points at the css so a `</style>` inside it can be pointed out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StyleBlock {
+    /// The css as written.
+    pub css: String,
+    /// Where the css starts.
+    pub span: Span,
+}
+
+/// Anything that can show up in the body, or inside a paragraph.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Node {
+    /// `#MAEK PARAGRAF ... #OIC`, with its own variable scope.
+    Paragraph(Paragraph),
+    /// `#MAEK LIST ... #OIC` or `#MAEK NUMBERZ ... #OIC`
+    List(List),
+    /// `#MAEK TABLE ... #OIC`
+    Table(Table),
+    /// `#MAEK SECTION ... #OIC`, `#MAEK DIV ... #OIC` and the rest of the elements that just group body content.
+    Section(Section),
+    /// `#MAEK QUOTE #FROM url #MKAY ... #OIC`
+    Quote(Quote),
+    /// `#GIMMEH RULE`
+    Rule,
+    /// `#MAEK CODE language` up to `#KTHX CODE`
+    Code(CodeBlock),
+    /// `#GIMMEH HEADR level ... #MKAY`
+    Heading(Heading),
+    /// `#GIMMEH SOUNDZ url #MKAY`
+    Audio(Media),
+    /// `#GIMMEH VIDZ url #MKAY`
+    Video(Media),
+    /// `#OBTW ... #TLDR`
+    Comment(String),
+    /// `#I HAZ name #IT IZ value #MKAY`
+    Define(VariableDefine),
+    /// Text level content.
+    Inline(Inline),
+}
+
+/// A paragraph, the optional variable definition at the very start is scoped to the paragraph.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Paragraph {
+    /// The variable scoped to the paragraph, if there is one.
+    pub variable: Option<VariableDefine>,
+    /// Everything after the variable definition.
+    pub content: Vec<Node>,
+}
+
+/// Whether a list is bulleted or numbered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListKind {
+    /// `#MAEK LIST`, a `<ul>`.
+    Bullets,
+    /// `#MAEK NUMBERZ`, an `<ol>`.
+    Numbers,
+}
+
+/// A list, numbered lists can also say what number to start at (`#STARTIN AT 3 #MKAY`)
+/// and how to count (`#LOOKIN LIEK roman #MKAY`), both have to come before the first item.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct List {
+    /// Bulleted or numbered.
+    pub kind: ListKind,
+    /// `#STARTIN AT`, numbered lists only.
+    pub start: Option<Setting>,
+    /// `#LOOKIN LIEK`, numbered lists only.
+    pub style: Option<Setting>,
+    /// The items, in order.
+    pub items: Vec<ListItem>,
+}
+
+/// The value of a setting exactly as written, with the span semantic analysis points at if it doesnt make sense.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Setting {
+    /// The value itself.
+    pub value: String,
+    /// Where the value starts.
+    pub span: Span,
+}
+
+/// A table, one `#MAEK ROW ... #OIC` after another.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Table {
+    /// The rows, in order.
+    pub rows: Vec<Row>,
+}
+
+/// A row of cells, the span points at its `#MAEK ROW` so a row that doesnt fit the table can be pointed out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Row {
+    /// The cells, in order.
+    pub cells: Vec<Cell>,
+    /// Where the `#MAEK ROW` is.
+    pub span: Span,
+}
+
+/// `#GIMMEH HEADER CELL ... #MKAY` or `#GIMMEH CELL ... #MKAY`, holding the same things a list item can.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cell {
+    /// Whether this is a `<th>` rather than a `<td>`.
+    pub header: bool,
+    /// What the cell holds.
+    pub content: Vec<Node>,
+}
+
+/// Which element a section turns into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SectionKind {
+    /// `#MAEK SECTION`, a `<section>`.
+    Section,
+    /// `#MAEK ARTICLE`, an `<article>`.
+    Article,
+    /// `#MAEK HEADER`, a `<header>`.
+    Header,
+    /// `#MAEK FOOTER`, a `<footer>`.
+    Footer,
+    /// `#MAEK NAV`, a `<nav>`.
+    Nav,
+    /// `#MAEK DIV`, a `<div>`.
+    Div,
+}
+
+/// An element that groups body content, it holds anything the body can except the head.
+/// Headers and footers cant have another header or footer anywhere inside them (the parser reports it).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Section {
+    /// Which element it is.
+    pub kind: SectionKind,
+    /// What the section holds.
+    pub content: Vec<Node>,
+}
+
+/// A block quote, holding the same things a section can.
+/// `#FROM url #MKAY` says where the quote came from, if its there it has to come first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Quote {
+    /// The url after `#FROM`, if there is one.
+    pub cite: Option<Media>,
+    /// What the quote holds.
+    pub content: Vec<Node>,
+}
+
+/// A block of code, kept exactly as it was written (tags, whitespace and all).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeBlock {
+    /// The word after `#MAEK CODE`, if there is one.
+    pub language: Option<String>,
+    /// The code as written.
+    pub code: String,
+}
+
+/// A heading, the level is always between 1 and 6 (the parser rejects anything else).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Heading {
+    /// 1 for an `<h1>` up to 6 for an `<h6>`.
+    pub level: u8,
+    /// The text of the heading.
+    pub content: Vec<Inline>,
+}
+
+/// One `#GIMMEH ITEM ... #MKAY`, any mix of inline content and nested lists, items are allowed to be empty.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ListItem {
+    /// What the item holds.
+    pub content: Vec<Node>,
+}
+
+/// Text level content.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Inline {
+    /// A run of text, kept exactly as written (surrounding whitespace included), it only gets escaped on the way out.
+    Text(String),
+    /// `#GIMMEH BOLD ... #MKAY`, `#GIMMEH ITALICS ... #MKAY` and the rest of the text styles,
+    /// each of which can hold any other inline content, more styled text included.
+    Styled(Style, Vec<Inline>),
+    /// `#GIMMEH NEWLINE`, a `<br>`.
+    Newline,
+    /// `#LEMME SEE name #MKAY`, replaced with the variable's value.
+    Variable(VariableUse),
+    /// `#GIMMEH LINK url #IT SEZ ... #MKAY`
+    Link(Link),
+    /// `#GIMMEH PIKCHUR url #WIDF w #HITE h #IT SEZ alt text #MKAY`
+    Image(Image),
+    /// `#GIMMEH HTML markup #MKAY`, written to the output exactly as is.
+    Raw(String),
+}
+
+/// Which element styled text turns into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Style {
+    /// `#GIMMEH BOLD`, a `<b>`.
+    Bold,
+    /// `#GIMMEH ITALICS`, an `<i>`.
+    Italics,
+    /// `#GIMMEH UNDERLINE`, a `<u>`.
+    Underline,
+    /// `#GIMMEH STRIKETHRU`, an `<s>`.
+    Strikethrough,
+    /// `#GIMMEH CODE`, a `<code>`.
+    Code,
+    /// `#GIMMEH SUBSCRIPT`, a `<sub>`.
+    Subscript,
+    /// `#GIMMEH SUPERSCRIPT`, a `<sup>`.
+    Superscript,
+    /// `#GIMMEH HIGHLIGHT`, a `<mark>`.
+    Highlight,
+    /// `#GIMMEH SMOL`, a `<small>`.
+    Small,
+}
+
+/// A link, the text can hold the same things bold and italics can.
+/// `span` points at the url so a bad one can be underlined.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Link {
+    /// Where the link goes.
+    pub href: String,
+    /// Where the url starts.
+    pub span: Span,
+    /// The text after `#IT SEZ`.
+    pub content: Vec<Inline>,
+}
+
+/// An image, width and height are optional but the alt text isnt (semantic analysis reports it missing).
+/// `#IT SEZ #MKAY` with nothing after it is how an image thats only decoration says so, that gives an empty alt.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    /// The image url.
+    pub src: Media,
+    /// The text after `#IT SEZ`, `None` if it was left off.
+    pub alt: Option<String>,
+    /// `#WIDF`, if given.
+    pub width: Option<Setting>,
+    /// `#HITE`, if given.
+    pub height: Option<Setting>,
+    /// Where the `#GIMMEH PIKCHUR` keyword is, for errors about the image as a whole.
+    pub span: Span,
+}
+
+/// The source url of an audio, video or image element, or the url a quote came from or the head links to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Media {
+    /// The url as written.
+    pub src: String,
+    /// Where the url starts.
+    pub span: Span,
+}
+
+/// `#I HAZ name #IT IZ value #MKAY`, the span points at the name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VariableDefine {
+    /// The variable's name, always one word.
+    pub name: String,
+    /// What the variable is replaced with.
+    pub value: String,
+    /// Where the name is.
+    pub span: Span,
+}
+
+/// `#LEMME SEE name #MKAY`, the span points at the name so scoping errors can underline it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VariableUse {
+    /// The name being looked up.
+    pub name: String,
+    /// Where the name is.
+    pub span: Span,
+}
@@ -0,0 +1,106 @@
+//! The driver that runs the lexer, the syntax analyzer and semantic analysis one after the other.
+
+use crate::ast::Document;
+use crate::diagnostics::CompileError;
+use crate::lexer::LolcodeLexicalAnalyzer;
+use crate::parser::{LolcodeSyntaxAnalyzer, SyntaxAnalyzer};
+use crate::semantic::{LolcodeSemanticAnalyzer, SemanticAnalyzer};
+use crate::token::{Span, SpannedToken, Token};
+use crate::Options;
+
+/// Runs every phase of compilation over a source string.
+pub trait Compiler {
+/// Begin the compilation process (entry point).
+/// Returns the generated html, or every error found (in source order, with any warnings mixed in).
+fn compile(&mut self, source: &str) -> Result<String, Vec<CompileError>>;
+/// Warnings from the last call to `compile`, only needed when it succeeded (a failed compile already returned them).
+fn warnings(&self) -> &[CompileError];
+}
+
+/// The default `Compiler`, wiring together `LolcodeLexicalAnalyzer`, `LolcodeSyntaxAnalyzer` and `LolcodeSemanticAnalyzer`.
+//struct that basically runs everything, holds lexer and syntaxer, semantic analysis gets its own analyzer once parsing is done
+pub struct LolcodeCompiler{
+    lexer: LolcodeLexicalAnalyzer,
+    current_token: SpannedToken,
+    syntaxer: LolcodeSyntaxAnalyzer,
+    options: Options,
+    warnings: Vec<CompileError>,
+}
+
+impl LolcodeCompiler{
+    /// Creates a compiler with the default `Options`.
+    //prepares everything to run properly
+    pub fn new() -> Self{
+        Self {
+            lexer: LolcodeLexicalAnalyzer::new(""),
+            current_token: SpannedToken { token: Token::Eof, span: Span::default() },
+            syntaxer: LolcodeSyntaxAnalyzer::new(),
+            options: Options::default(),
+            warnings: Vec::new(),
+        }
+    }
+    /// Creates a compiler that uses the given `Options`.
+    //same as new, but with settings other than the defaults (only the ones that change the generated html matter here, the file name is for diagnostics)
+    pub fn with_options(options: &Options) -> Self{
+        Self { options: options.clone(), ..Self::new() }
+    }
+    //pops token off vector and returns it, the lexer already rejected anything that isnt a real keyword so theres nothing left to check here
+    //once the vector runs dry it just keeps handing back the end of file token
+    fn next_token(&mut self) -> SpannedToken {
+        if let Some(candidate) = self.lexer.tokens.pop() {
+            self.current_token = candidate;
+        } else {
+            self.current_token.token = Token::Eof;
+        }
+        self.current_token.clone()
+    }
+    //basically just repeatedly calls next token to get tokens into the syntax analyzer, also completely disregards whitespace only text to make syntax analysis go smoother
+    //is it an "interesting" use of the next token function, yes, does it work though, also yes
+    fn parse(&mut self) -> Document{
+        while !self.lexer.tokens.is_empty() {
+            let tok = self.next_token();
+            if let Token::Text(text) = &tok.token && text.trim().is_empty() {
+                continue;
+            }
+            self.syntaxer.token_vector.push(tok);
+        }
+        self.syntaxer.parse_lolcode()
+    }
+}
+
+impl Default for LolcodeCompiler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Compiler for LolcodeCompiler{
+    //method opens a new lexer, then it begins tokenization which runs the entire string through it and puts tokens into the tokens vector
+    //the syntaxer gets reset too so the same compiler can be reused for another source
+    //once parsing is done the syntax tree is handed to semantic analysis, which is what actually produces the html
+    //lexical errors stop everything right away (parsing around missing tags would just make up more errors), but syntax errors dont
+    //the parser recovers and semantic analysis still runs on what it managed to build, so undefined variables get reported in the same go
+    fn compile(&mut self, source: &str) -> Result<String, Vec<CompileError>>{
+        self.lexer = LolcodeLexicalAnalyzer::new(source);
+        self.warnings.clear();
+        self.syntaxer = LolcodeSyntaxAnalyzer::new();
+        self.lexer.tokenize()?;
+        let document = self.parse();
+        let mut semantics = LolcodeSemanticAnalyzer::with_options(&self.options);
+        semantics.semantic_analysis(&document);
+        let mut errors = std::mem::take(&mut self.syntaxer.errors);
+        errors.append(&mut semantics.errors);
+        self.warnings = std::mem::take(&mut semantics.warnings);
+        self.warnings.sort_by_key(|warning| warning.location.offset);
+        if errors.is_empty() {
+            Ok(semantics.output)
+        } else {
+            errors.extend(self.warnings.iter().cloned());
+            errors.sort_by_key(|error| error.location.offset);
+            Err(errors)
+        }
+    }
+    fn warnings(&self) -> &[CompileError]{
+        &self.warnings
+    }
+}
@@ -0,0 +1,235 @@
+//! Errors reported by every phase of the compiler, and how they get shown to a person.
+
+use std::fmt;
+
+use crate::token::Span;
+
+/// Which phase of the compiler rejected the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// An unknown or badly formed tag.
+    Lexical,
+    /// Tags in an order the grammar doesnt allow.
+    Syntax,
+    /// Something that parses fine but doesnt make sense, like a variable that isnt in scope.
+    Semantic,
+}
+
+impl ErrorKind {
+    /// The phase on its own, without saying whether it was an error or a warning.
+    pub fn phase(&self) -> &'static str {
+        match self {
+            ErrorKind::Lexical => "lexical",
+            ErrorKind::Syntax => "syntax",
+            ErrorKind::Semantic => "static semantic",
+        }
+    }
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} error", self.phase())
+    }
+}
+
+/// Whether a diagnostic stops the html from being produced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// The html isnt produced.
+    Error,
+    /// Something that is probably a mistake but still compiles, the html comes out either way.
+    Warning,
+}
+
+/// How confident the compiler is that a suggested fix is what the person meant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Applicability {
+    /// The fix can be applied without looking at it, `Diagnostics::apply_suggestions` uses these.
+    MachineApplicable,
+    /// Probably right, but there was more than one reasonable option so a person should check.
+    MaybeIncorrect,
+}
+
+/// A fix for an error: replace the text under `span` with `replacement`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suggestion {
+    /// The text to replace.
+    pub span: Span,
+    /// What to put there instead.
+    pub replacement: String,
+    /// Whether the fix is safe to apply automatically.
+    pub applicability: Applicability,
+}
+
+/// An error found while compiling, along with where in the source it happened.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompileError {
+    /// The phase that found it.
+    pub kind: ErrorKind,
+    /// What went wrong, without the location.
+    pub message: String,
+    /// Where in the source it happened.
+    pub location: Span,
+    /// Whether it is an error or only a warning.
+    pub severity: Severity,
+    /// Fixes the compiler can offer for this error, usually empty.
+    pub suggestions: Vec<Suggestion>,
+}
+
+impl CompileError {
+    /// Creates an error with no suggestions.
+    pub fn new(kind: ErrorKind, message: impl Into<String>, location: Span) -> Self {
+        Self { kind, message: message.into(), location, severity: Severity::Error, suggestions: Vec::new() }
+    }
+    /// Creates a warning with no suggestions.
+    pub fn warning(kind: ErrorKind, message: impl Into<String>, location: Span) -> Self {
+        Self { severity: Severity::Warning, ..Self::new(kind, message, location) }
+    }
+    /// Whether this is a warning rather than an error.
+    pub fn is_warning(&self) -> bool {
+        self.severity == Severity::Warning
+    }
+    //"syntax error", "static semantic warning", etc
+    fn label(&self) -> String {
+        match self.severity {
+            Severity::Error => self.kind.to_string(),
+            Severity::Warning => format!("{} warning", self.kind.phase()),
+        }
+    }
+    /// Adds a fix to offer along with the error.
+    pub fn with_suggestion(mut self, suggestion: Suggestion) -> Self {
+        self.suggestions.push(suggestion);
+        self
+    }
+    /// Formats the error with the line it happened on and carets under the offending part.
+    //formats the error the same way rustc does, header first, then the file position, then the line itself with carets under the offending part
+    //the carets stop at the end of the line, so a tag that got split across lines only underlines the first piece
+    pub fn render(&self, file: &SourceFile) -> String {
+        let span = self.location;
+        let line_text = file.text.lines().nth(span.line - 1).unwrap_or("");
+        let line_number = span.line.to_string();
+        let gutter = " ".repeat(line_number.len());
+        //tabs are copied over so the carets still line up in editors that expand them
+        let indent: String = line_text
+            .chars()
+            .take(span.column - 1)
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect();
+        let width = file
+            .text
+            .get(span.offset..span.offset + span.len)
+            .and_then(|underlined| underlined.lines().next())
+            .map(|underlined| underlined.chars().count())
+            .unwrap_or(0)
+            .max(1);
+        format!(
+            "{}: {}\n{gutter}--> {}:{}:{}\n{gutter} |\n{line_number} | {line_text}\n{gutter} | {indent}{}",
+            self.label(),
+            self.message,
+            file.name,
+            span.line,
+            span.column,
+            "^".repeat(width),
+        )
+    }
+}
+
+impl fmt::Display for CompileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {} at {}:{}", self.label(), self.message, self.location.line, self.location.column)
+    }
+}
+
+impl std::error::Error for CompileError {}
+
+/// The file being compiled, only needed when turning a `CompileError` into something a person can read.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SourceFile {
+    /// The name shown in the location line, usually the path.
+    pub name: String,
+    /// The whole source.
+    pub text: String,
+}
+
+impl SourceFile {
+    /// Creates a source file from its name and contents.
+    pub fn new(name: &str, text: &str) -> Self {
+        Self { name: name.to_string(), text: text.to_string() }
+    }
+}
+
+/// Every error (and warning) from one compilation, together with the source they point into.
+/// Displaying it renders each one with its source snippet, one after the other, followed by a count of how many there were.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostics {
+    /// The source the errors point into.
+    pub file: SourceFile,
+    /// The errors and warnings, in source order.
+    pub items: Vec<CompileError>,
+}
+
+impl Diagnostics {
+    /// Groups errors with the source they came from.
+    pub fn new(file: SourceFile, items: Vec<CompileError>) -> Self {
+        Self { file, items }
+    }
+    /// Whether there arent any errors or warnings at all.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+    /// The errors and warnings, in source order.
+    pub fn iter(&self) -> std::slice::Iter<'_, CompileError> {
+        self.items.iter()
+    }
+    /// The source with every `MachineApplicable` suggestion applied.
+    //hands back the source with every machine applicable suggestion applied, anything else is left alone
+    //the edits are made back to front so the offsets of the earlier ones stay valid, overlapping ones after the first are skipped
+    pub fn apply_suggestions(&self) -> String {
+        let mut suggestions: Vec<&Suggestion> = self
+            .items
+            .iter()
+            .flat_map(|error| &error.suggestions)
+            .filter(|suggestion| suggestion.applicability == Applicability::MachineApplicable)
+            .collect();
+        suggestions.sort_by_key(|suggestion| std::cmp::Reverse(suggestion.span.offset));
+        let mut text = self.file.text.clone();
+        let mut limit = text.len();
+        for suggestion in suggestions {
+            let end = suggestion.span.offset + suggestion.span.len;
+            if end > limit {
+                continue;
+            }
+            text.replace_range(suggestion.span.offset..end, &suggestion.replacement);
+            limit = suggestion.span.offset;
+        }
+        text
+    }
+}
+
+impl fmt::Display for Diagnostics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, error) in self.items.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            writeln!(f, "{}", error.render(&self.file))?;
+        }
+        //counted the way rustc does it, "aborting due to 2 previous errors; 1 warning emitted"
+        let warnings = self.items.iter().filter(|error| error.is_warning()).count();
+        let errors = self.items.len() - warnings;
+        let plural = |count: usize, what: &str| if count == 1 { format!("1 {}", what) } else { format!("{} {}s", count, what) };
+        match (errors, warnings) {
+            (0, 0) => Ok(()),
+            (0, warnings) => writeln!(f, "\nwarning: {} emitted", plural(warnings, "warning")),
+            (errors, 0) => writeln!(f, "\nerror: aborting due to {}", plural(errors, "previous error")),
+            (errors, warnings) => writeln!(
+                f,
+                "\nerror: aborting due to {}; {} emitted",
+                plural(errors, "previous error"),
+                plural(warnings, "warning"),
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Diagnostics {}
@@ -0,0 +1,194 @@
+//! The keyword registry, the one place every lolcode keyword is described.
+//!
+//! The lexer matches source text against `spelling`, the parser uses `kind` and `block` to find its way
+//! back after an error, and semantic analysis takes the element name it writes out from `html`.
+//! Adding a keyword means adding a `Token` variant and a row here, then teaching the parser what it means.
+
+use std::collections::HashMap;
+use std::mem::{self, Discriminant};
+use std::sync::OnceLock;
+
+use crate::token::Token;
+
+/// How a keyword behaves structurally.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeywordKind {
+    /// Starts a construct that some closer has to finish (`#MAEK PARAGRAF`, `#GIMMEH BOLD`).
+    Opener,
+    /// Finishes whatever construct is open (`#OIC`, `#MKAY`).
+    Closer,
+    /// Stands on its own (`#GIMMEH NEWLINE`) or sits in the middle of a construct (`#IT IZ`).
+    Void,
+}
+
+/// Everything there is to know about one keyword.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Keyword {
+    /// The token the lexer produces for it.
+    pub token: Token,
+    /// Uppercase spelling, words separated by single spaces. Source text matches case insensitively with any whitespace between words.
+    pub spelling: &'static str,
+    /// What the keyword does to the construct around it.
+    pub kind: KeywordKind,
+    /// Name of the html element the construct turns into, if it turns into one.
+    pub html: Option<&'static str>,
+    /// Whether the keyword starts a block level construct, these are where the parser picks back up after an error.
+    pub block: bool,
+}
+
+const fn keyword(token: Token, spelling: &'static str, kind: KeywordKind, html: Option<&'static str>, block: bool) -> Keyword {
+    Keyword { token, spelling, kind, html, block }
+}
+
+use KeywordKind::{Closer, Opener, Void};
+
+/// Every keyword in the language.
+pub static KEYWORDS: &[Keyword] = &[
+    keyword(Token::Hai, "#HAI", Opener, Some("html"), false),
+    keyword(Token::Kthxbye, "#KTHXBYE", Closer, None, false),
+    keyword(Token::Obtw, "#OBTW", Opener, None, true),
+    keyword(Token::Tldr, "#TLDR", Closer, None, false),
+    keyword(Token::MaekHead, "#MAEK HEAD", Opener, Some("head"), true),
+    keyword(Token::Oic, "#OIC", Closer, None, false),
+    keyword(Token::GimmehTitle, "#GIMMEH TITLE", Opener, Some("title"), false),
+    keyword(Token::Mkay, "#MKAY", Closer, None, false),
+    keyword(Token::MaekParagraf, "#MAEK PARAGRAF", Opener, Some("p"), true),
+    keyword(Token::GimmehBold, "#GIMMEH BOLD", Opener, Some("b"), false),
+    keyword(Token::GimmehItalics, "#GIMMEH ITALICS", Opener, Some("i"), false),
+    keyword(Token::MaekList, "#MAEK LIST", Opener, Some("ul"), true),
+    keyword(Token::GimmehItem, "#GIMMEH ITEM", Opener, Some("li"), false),
+    keyword(Token::GimmehNewline, "#GIMMEH NEWLINE", Void, Some("br"), false),
+    keyword(Token::GimmehSoundz, "#GIMMEH SOUNDZ", Opener, Some("audio"), true),
+    keyword(Token::GimmehVidz, "#GIMMEH VIDZ", Opener, Some("iframe"), true),
+    keyword(Token::IHaz, "#I HAZ", Opener, None, true),
+    keyword(Token::ItIz, "#IT IZ", Void, None, false),
+    keyword(Token::LemmeSee, "#LEMME SEE", Opener, None, false),
+    keyword(Token::GimmehHtml, "#GIMMEH HTML", Opener, None, false),
+    keyword(Token::MaekNumberz, "#MAEK NUMBERZ", Opener, Some("ol"), true),
+    keyword(Token::StartinAt, "#STARTIN AT", Opener, None, false),
+    keyword(Token::LookinLiek, "#LOOKIN LIEK", Opener, None, false),
+    //the element depends on the level (h1 to h6), so semantic analysis builds the name itself
+    keyword(Token::GimmehHeadr, "#GIMMEH HEADR", Opener, None, true),
+    keyword(Token::GimmehLink, "#GIMMEH LINK", Opener, Some("a"), false),
+    keyword(Token::ItSez, "#IT SEZ", Void, None, false),
+    keyword(Token::GimmehPikchur, "#GIMMEH PIKCHUR", Opener, Some("img"), false),
+    keyword(Token::Widf, "#WIDF", Void, None, false),
+    keyword(Token::Hite, "#HITE", Void, None, false),
+    keyword(Token::MaekTable, "#MAEK TABLE", Opener, Some("table"), true),
+    keyword(Token::MaekRow, "#MAEK ROW", Opener, Some("tr"), false),
+    keyword(Token::GimmehHeaderCell, "#GIMMEH HEADER CELL", Opener, Some("th"), false),
+    keyword(Token::GimmehCell, "#GIMMEH CELL", Opener, Some("td"), false),
+    keyword(Token::MaekCode, "#MAEK CODE", Opener, Some("pre"), true),
+    keyword(Token::KthxCode, "#KTHX CODE", Closer, None, false),
+    keyword(Token::MaekSection, "#MAEK SECTION", Opener, Some("section"), true),
+    keyword(Token::MaekArticle, "#MAEK ARTICLE", Opener, Some("article"), true),
+    keyword(Token::MaekHeader, "#MAEK HEADER", Opener, Some("header"), true),
+    keyword(Token::MaekFooter, "#MAEK FOOTER", Opener, Some("footer"), true),
+    keyword(Token::MaekNav, "#MAEK NAV", Opener, Some("nav"), true),
+    keyword(Token::MaekDiv, "#MAEK DIV", Opener, Some("div"), true),
+    keyword(Token::MaekQuote, "#MAEK QUOTE", Opener, Some("blockquote"), true),
+    keyword(Token::From, "#FROM", Opener, None, false),
+    //not #GIMMEH LINE, thats one typo away from #GIMMEH LINK
+    keyword(Token::GimmehRule, "#GIMMEH RULE", Void, Some("hr"), true),
+    keyword(Token::GimmehUnderline, "#GIMMEH UNDERLINE", Opener, Some("u"), false),
+    keyword(Token::GimmehStrikethru, "#GIMMEH STRIKETHRU", Opener, Some("s"), false),
+    keyword(Token::GimmehCode, "#GIMMEH CODE", Opener, Some("code"), false),
+    keyword(Token::GimmehSubscript, "#GIMMEH SUBSCRIPT", Opener, Some("sub"), false),
+    keyword(Token::GimmehSuperscript, "#GIMMEH SUPERSCRIPT", Opener, Some("sup"), false),
+    keyword(Token::GimmehHighlight, "#GIMMEH HIGHLIGHT", Opener, Some("mark"), false),
+    keyword(Token::GimmehSmol, "#GIMMEH SMOL", Opener, Some("small"), false),
+    keyword(Token::GimmehDescripshun, "#GIMMEH DESCRIPSHUN", Opener, Some("meta"), false),
+    keyword(Token::GimmehAuthor, "#GIMMEH AUTHOR", Opener, Some("meta"), false),
+    keyword(Token::GimmehKeywordz, "#GIMMEH KEYWORDZ", Opener, Some("meta"), false),
+    keyword(Token::GimmehViewport, "#GIMMEH VIEWPORT", Opener, Some("meta"), false),
+    keyword(Token::GimmehStylesheet, "#GIMMEH STYLESHEET", Opener, Some("link"), false),
+    keyword(Token::MaekStyle, "#MAEK STYLE", Opener, Some("style"), false),
+    keyword(Token::KthxStyle, "#KTHX STYLE", Closer, None, false),
+    keyword(Token::GimmehScript, "#GIMMEH SCRIPT", Opener, Some("script"), false),
+    keyword(Token::GimmehIcon, "#GIMMEH ICON", Opener, Some("link"), false),
+];
+
+//the lookups below happen for every tag in the file (and every token the parser checks), so they go through maps built the first time theyre needed instead of scanning the registry each time
+struct Index {
+    by_token: HashMap<Discriminant<Token>, &'static Keyword>,
+    by_spelling: HashMap<&'static str, &'static Keyword>,
+    by_first_word: HashMap<&'static str, Vec<&'static Keyword>>,
+}
+
+fn index() -> &'static Index {
+    static INDEX: OnceLock<Index> = OnceLock::new();
+    INDEX.get_or_init(|| {
+        let mut index = Index { by_token: HashMap::new(), by_spelling: HashMap::new(), by_first_word: HashMap::new() };
+        for keyword in KEYWORDS {
+            index.by_token.insert(mem::discriminant(&keyword.token), keyword);
+            index.by_spelling.insert(keyword.spelling, keyword);
+            let first_word = keyword.spelling.split(' ').next().unwrap_or(keyword.spelling);
+            index.by_first_word.entry(first_word).or_default().push(keyword);
+        }
+        index
+    })
+}
+
+/// Finds a keyword by its spelling. Expects the normalized form (uppercase, single spaces).
+pub fn by_spelling(spelling: &str) -> Option<&'static Keyword> {
+    index().by_spelling.get(spelling).copied()
+}
+
+/// Finds the registry entry for a token, `None` for `Text`, `Verbatim` and `Eof`.
+pub fn by_token(token: &Token) -> Option<&'static Keyword> {
+    index().by_token.get(&mem::discriminant(token)).copied()
+}
+
+/// Every keyword whose spelling starts with `word` as its whole first word (`#GIMMEH` gives all the `#GIMMEH ...` keywords). Expects uppercase.
+pub fn by_first_word(word: &str) -> &'static [&'static Keyword] {
+    index().by_first_word.get(word).map_or(&[], Vec::as_slice)
+}
+
+/// The html element a keyword maps to. Only call this for keywords that have one.
+pub fn html_tag(token: &Token) -> &'static str {
+    match by_token(token).and_then(|keyword| keyword.html) {
+        Some(tag) => tag,
+        None => panic!("{:?} has no html element in the keyword registry", token),
+    }
+}
+
+/// Keywords spelled close enough to `tag` that it was probably a typo of one of them, closest first.
+/// Only the keywords tied for closest are returned, and nothing if even those are too far off to be a likely typo.
+pub fn similar(tag: &str) -> Vec<&'static Keyword> {
+    //roughly one mistake for every three characters, but always at least one
+    let allowed = (tag.trim_start_matches('#').chars().count() / 3).max(1);
+    let distances: Vec<(usize, &'static Keyword)> =
+        KEYWORDS.iter().map(|keyword| (edit_distance(tag, keyword.spelling), keyword)).collect();
+    match distances.iter().map(|(distance, _)| *distance).min() {
+        Some(best) if best <= allowed => distances
+            .into_iter()
+            .filter(|(distance, _)| *distance == best)
+            .map(|(_, keyword)| keyword)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+//optimal string alignment distance, so swapping two letters (MAKE for MAEK) only counts as one edit
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut rows = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in rows[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut best = (rows[i - 1][j] + 1).min(rows[i][j - 1] + 1).min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                best = best.min(rows[i - 2][j - 2] + 1);
+            }
+            rows[i][j] = best;
+        }
+    }
+    rows[a.len()][b.len()]
+}
@@ -0,0 +1,345 @@
+//! Lexical analysis: turns .lol source text into a stream of spanned tokens.
+
+use crate::diagnostics::{Applicability, CompileError, ErrorKind, Suggestion};
+use crate::keywords::{self, Keyword, KEYWORDS};
+use crate::token::{Span, SpannedToken, Token};
+
+/// The character level interface of the lexer.
+pub trait LexicalAnalyzer {
+/// Return the next character from the input.
+/// If input is exhausted, returns '\0'.
+fn get_char(&mut self) -> char;
+/// Add a character to the current potential token.
+fn add_char(&mut self, c: char);
+/// Lookup a potential token to determine if it is valid.
+/// Returns true if a valid token/lexeme, false otherwise.
+fn lookup(&self, s: &str) -> bool;
+}
+
+/// Lexical analyzer for lolcode.
+/// Call `tokenize` once, after that `tokens` holds every token in reverse order (so `pop` hands them back front to back), ending with `Token::Eof`.
+/// Unrecognized tags dont stop tokenizing, they are all collected in `errors` (and `tokenize` returns them).
+//struct that stores everything related to the lexical analyzer, including the afformentioned tokens vector, the valid tokens themselves live in the keywords module
+pub struct LolcodeLexicalAnalyzer{
+    input: Vec<char>,
+    position: usize,
+    //where the next character from get_char lives in the source, kept alongside position so tokens can remember where they came from
+    location: Span,
+    current_build: String,
+    build_span: Span,
+    //byte offset just past the last non whitespace character of the text being built, escapes make the text shorter than the source so the length cant come from the text
+    build_end: usize,
+    /// The tokens found so far, last one first.
+    pub tokens: Vec<SpannedToken>,
+    /// Every unrecognized tag found so far.
+    pub errors: Vec<CompileError>,
+}
+
+impl LolcodeLexicalAnalyzer{
+    /// Creates a lexer over `source`, nothing is read until `tokenize` is called.
+    pub fn new(source: &str) -> Self{
+        Self {
+            input: source.chars().collect(),
+            position: 0,
+            location: Span::default(),
+            current_build: String::new(),
+            build_span: Span::default(),
+            build_end: 0,
+            tokens: Vec::new(),
+            errors: Vec::new(),
+        }
+    }
+    /// The token for a keyword spelling, `None` if it isnt a keyword.
+    //turns an (already uppercased, single spaced) tag into its token, None if it isnt one of ours
+    pub fn keyword(&self, s: &str) -> Option<Token> {
+        keywords::by_spelling(s).map(|keyword| keyword.token.clone())
+    }
+    /// Splits the whole source into tokens, handing back every unrecognized tag if there were any.
+    //in simple terms, everything is text until a '#' shows up, then the tag gets matched against the keyword registry
+    //every keyword is tried at the current position and the longest one that fits wins, so there's no limit on how many words a keyword can have
+    //words in a keyword can be split by any amount of whitespace (newlines included) and case doesnt matter, but the keyword has to end at whitespace or the end of the file
+    //exactly one whitespace character after a tag is treated as the delimiter and dropped, anything past that belongs to the text that follows
+    //if nothing matches, the invalid tag gets recorded as an error and tokenizing keeps going so every bad tag in the file gets reported at once
+    //code and style blocks (#MAEK CODE ... #KTHX CODE, #MAEK STYLE ... #KTHX STYLE) are the exception to all of this, everything inside them is kept exactly as written, see lex_verbatim
+    //a '#' that should just be a '#' in the text is written as "\#", the backslash is dropped and the '#' goes into the text like any other character
+    //a backslash in front of anything else is left alone
+    //every token also gets the span of its first character (for text thats the first non whitespace character, since thats what a person would point at)
+    //TLDR, this method both tokenizes input and performs lexical analysis on the tokens in one fell swoop
+    pub fn tokenize(&mut self) -> Result<(), Vec<CompileError>> {
+        loop {
+            if self.peek() == Some('#') {
+                self.push_text();
+                self.lex_tag();
+                continue;
+            }
+
+            let here = self.location;
+            let mut c = self.get_char();
+            if c == '\0' {
+                self.push_text();
+                self.tokens.push(SpannedToken { token: Token::Eof, span: self.location });
+                break;
+            }
+            if c == '\\' && self.peek() == Some('#') {
+                c = self.get_char();
+            }
+            if !c.is_whitespace() {
+                if self.current_build.trim().is_empty() {
+                    self.build_span = here;
+                }
+                self.build_end = self.location.offset;
+            }
+            self.add_char(c);
+        }
+
+        self.tokens.reverse();
+        if self.errors.is_empty() {
+            Ok(())
+        } else {
+            Err(self.errors.clone())
+        }
+    }
+    fn peek(&self) -> Option<char> {
+        self.input.get(self.position).copied()
+    }
+    //reads one tag starting at the '#' under the cursor, along with the whitespace delimiter after it
+    fn lex_tag(&mut self) {
+        self.build_span = self.location;
+        //only keywords that start with the same word can match, which saves trying every keyword at every '#'
+        let first_word: String = self.input[self.position..].iter().take_while(|c| !c.is_whitespace()).map(|c| c.to_ascii_uppercase()).collect();
+        let candidates = keywords::by_first_word(&first_word);
+        let (end, glued) = match candidates.iter().filter_map(|keyword| self.match_spelling(keyword.spelling)).max() {
+            Some(end) => (end, None),
+            None => {
+                let end = self.unknown_tag_end();
+                let glued = self.glued_keyword(end).map(|(keyword, keyword_end)| {
+                    (keyword, self.input[self.position..keyword_end].iter().map(|c| c.len_utf8()).sum())
+                });
+                (end, glued)
+            }
+        };
+        while self.position < end {
+            let c = self.get_char();
+            self.add_char(c);
+        }
+        let len = self.location.offset - self.build_span.offset;
+        let tag = normalize(&self.current_build);
+        self.push_tag(&tag, len, glued);
+        let verbatim = match self.tokens.last().map(|token| &token.token) {
+            Some(Token::MaekCode) => Some((Token::KthxCode, "code")),
+            Some(Token::MaekStyle) => Some((Token::KthxStyle, "style")),
+            _ => None,
+        };
+        if let Some((closer, what)) = verbatim {
+            let opener = self.tokens[self.tokens.len() - 1].span;
+            self.lex_verbatim(opener, closer, what);
+            return;
+        }
+        if self.peek().is_some_and(char::is_whitespace) {
+            self.get_char();
+        }
+    }
+    //code and style blocks are the only places where tags and whitespace dont mean anything, so they get read here all in one go instead of by tokenize
+    //the rest of the opening line is handed over as text (a code block's language, if theres anything there), then every character after that line is the block itself, right up to the closer
+    //the line break right before the closer just puts it on its own line, so it isnt part of the block
+    fn lex_verbatim(&mut self, opener: Span, closer_token: Token, what: &str) {
+        let mut language = String::new();
+        let mut language_span = self.location;
+        while let Some(c) = self.peek() {
+            if c == '\n' {
+                break;
+            }
+            if !c.is_whitespace() && language.trim().is_empty() {
+                language_span = self.location;
+            }
+            language.push(self.get_char());
+        }
+        if !language.trim().is_empty() {
+            language_span.len = language.trim().len();
+            self.tokens.push(SpannedToken { token: Token::Text(language), span: language_span });
+        }
+        self.get_char();
+
+        let code_span = self.location;
+        let mut code = String::new();
+        let closer = closer_token.lexeme().to_string();
+        loop {
+            if self.peek() == Some('#') && let Some(end) = self.match_spelling(&closer) {
+                if code.ends_with('\n') {
+                    code.pop();
+                    if code.ends_with('\r') {
+                        code.pop();
+                    }
+                }
+                let len = self.location.offset - code_span.offset;
+                self.tokens.push(SpannedToken { token: Token::Verbatim(code), span: Span { len, ..code_span } });
+                let closer_span = self.location;
+                while self.position < end {
+                    self.get_char();
+                }
+                let len = self.location.offset - closer_span.offset;
+                self.tokens.push(SpannedToken { token: closer_token, span: Span { len, ..closer_span } });
+                if self.peek().is_some_and(char::is_whitespace) {
+                    self.get_char();
+                }
+                return;
+            }
+            match self.peek() {
+                Some(_) => code.push(self.get_char()),
+                None => break,
+            }
+        }
+        self.errors.push(CompileError::new(ErrorKind::Lexical, format!("{} block is never closed, expected {} before the end of the file", what, closer), opener));
+    }
+    //checks if a keyword is spelled out at the cursor, hands back the position just past it if so
+    fn match_spelling(&self, spelling: &str) -> Option<usize> {
+        let pos = self.spelling_end(spelling)?;
+        match self.input.get(pos) {
+            Some(c) if !c.is_whitespace() => None,
+            _ => Some(pos),
+        }
+    }
+    //the part of match_spelling that doesnt care what comes after the keyword
+    fn spelling_end(&self, spelling: &str) -> Option<usize> {
+        let mut pos = self.position;
+        for (i, word) in spelling.split(' ').enumerate() {
+            if i > 0 {
+                let start = pos;
+                while self.input.get(pos).is_some_and(|c| c.is_whitespace()) {
+                    pos += 1;
+                }
+                if pos == start {
+                    return None;
+                }
+            }
+            for expected in word.chars() {
+                if self.input.get(pos).map(|c| c.to_ascii_uppercase()) != Some(expected) {
+                    return None;
+                }
+                pos += 1;
+            }
+        }
+        Some(pos)
+    }
+    //a real keyword with punctuation stuck to the end of it ("#MKAY," or "#OIC)"), the keyword and where it stops if so
+    //only punctuation counts, "#GIMMEH BOLDX" is a typo of the keyword and not the keyword followed by an X
+    fn glued_keyword(&self, end: usize) -> Option<(&'static Keyword, usize)> {
+        KEYWORDS
+            .iter()
+            .filter_map(|keyword| self.spelling_end(keyword.spelling).map(|pos| (keyword, pos)))
+            .filter(|(_, pos)| *pos < end && !self.input[*pos].is_alphanumeric() && !self.input[*pos].is_whitespace())
+            .max_by_key(|(_, pos)| *pos)
+    }
+    //how much of the input a tag that isnt a keyword covers, normally just the first word
+    //if that word starts some longer keyword (like "#GIMMEH BOLDX") the next word is pulled in too, so the error shows what the person was going for
+    //same goes for when the first word on its own doesnt look like anything but the two words together look like a typo of a keyword ("#MAKE PARAGRAF")
+    fn unknown_tag_end(&self) -> usize {
+        let word_end = |mut pos: usize| {
+            while self.input.get(pos).is_some_and(|c| !c.is_whitespace()) {
+                pos += 1;
+            }
+            pos
+        };
+        let end = word_end(self.position);
+        let first = normalize(&self.input[self.position..end].iter().collect::<String>());
+        let mut pos = end;
+        while self.input.get(pos).is_some_and(|c| c.is_whitespace()) {
+            pos += 1;
+        }
+        if pos == self.input.len() {
+            return end;
+        }
+        let two_word_end = word_end(pos);
+        let prefix = KEYWORDS.iter().any(|keyword| keyword.spelling.starts_with(&format!("{} ", first)));
+        let two_words = normalize(&self.input[self.position..two_word_end].iter().collect::<String>());
+        let typo = keywords::similar(&first).is_empty() && !keywords::similar(&two_words).is_empty();
+        if prefix || typo { two_word_end } else { end }
+    }
+    //finishes off a tag, if its not a real keyword it gets recorded as a lexical error and dropped
+    //when the tag looks like a typo of a real keyword, the error suggests it, and if theres only one close keyword the fix is safe to apply automatically
+    //a keyword with punctuation stuck to it (glued holds the keyword and how many bytes of the tag it covers) just needs a space after the keyword, nothing the person wrote gets replaced
+    fn push_tag(&mut self, tag: &str, len: usize, glued: Option<(&'static Keyword, usize)>) {
+        let span = Span { len, ..self.build_span };
+        match self.keyword(tag) {
+            Some(token) => self.tokens.push(SpannedToken { token, span }),
+            None if let Some((keyword, keyword_len)) = glued => {
+                let (written, rest) = self.current_build.split_at(keyword_len);
+                let error = CompileError::new(
+                    ErrorKind::Lexical,
+                    format!("unknown tag {}; did you mean {} {}? (a keyword has to be followed by whitespace)", tag, keyword.spelling, rest),
+                    span,
+                )
+                .with_suggestion(Suggestion {
+                    span: Span { len: keyword_len, ..self.build_span },
+                    replacement: format!("{} ", written),
+                    applicability: Applicability::MachineApplicable,
+                });
+                self.errors.push(error);
+            }
+            None => {
+                let similar = keywords::similar(tag);
+                let error = match similar.first() {
+                    Some(keyword) => CompileError::new(
+                        ErrorKind::Lexical,
+                        format!("unknown tag {}; did you mean {}?", tag, keyword.spelling),
+                        span,
+                    )
+                    .with_suggestion(Suggestion {
+                        span,
+                        replacement: keyword.spelling.to_string(),
+                        applicability: if similar.len() == 1 { Applicability::MachineApplicable } else { Applicability::MaybeIncorrect },
+                    }),
+                    None => CompileError::new(ErrorKind::Lexical, format!("unknown tag {}", tag), span),
+                };
+                self.errors.push(error);
+            }
+        }
+        self.current_build.clear();
+    }
+    //finishes off a run of text (if there is one), the span only covers the text itself and not the whitespace around it
+    fn push_text(&mut self) {
+        if !self.current_build.is_empty() {
+            let text = std::mem::take(&mut self.current_build);
+            let len = self.build_end.saturating_sub(self.build_span.offset);
+            let span = Span { len, ..self.build_span };
+            self.tokens.push(SpannedToken { token: Token::Text(text), span });
+        }
+    }
+}
+
+//uppercases a tag and squashes the whitespace between its words down to single spaces, the form spellings are stored in
+fn normalize(tag: &str) -> String {
+    tag.split_whitespace().collect::<Vec<_>>().join(" ").to_uppercase()
+}
+
+impl LexicalAnalyzer for LolcodeLexicalAnalyzer{
+    //this and the add char method basically just grab the next character in the input, then adds the character to the current build
+    //get_char also moves the location forward so the next token knows where it starts
+    fn get_char(&mut self) -> char{
+        if self.position < self.input.len(){
+            let c = self.input[self.position];
+            self.position += 1;
+            self.location.offset += c.len_utf8();
+            if c == '\n' {
+                self.location.line += 1;
+                self.location.column = 1;
+            } else {
+                self.location.column += 1;
+            }
+            c
+        } else{
+            '\0'
+        }
+    }
+
+    fn add_char(&mut self, c: char){
+        self.current_build.push(c);
+    }
+
+    fn lookup(&self, s: &str) -> bool{
+        if !s.starts_with('#'){
+            return false;
+        }
+        self.keyword(s).is_some()
+    }
+}
@@ -0,0 +1,66 @@
+//! A compiler from lolcode markdown (.lol files) to html.
+//!
+//! Most callers only need [`compile_str`]. The individual phases live in [`lexer`], [`parser`]
+//! and [`semantic`], the tree passed between the last two is in [`ast`], and [`LolcodeCompiler`]
+//! strings them together if you want to drive them yourself. Every keyword the phases know
+//! about is listed once in [`keywords`].
+
+#![warn(missing_docs)]
+
+pub mod ast;
+pub mod compiler;
+pub mod diagnostics;
+pub mod keywords;
+pub mod lexer;
+pub mod parser;
+pub mod semantic;
+pub mod token;
+pub mod url;
+
+pub use compiler::{Compiler, LolcodeCompiler};
+pub use diagnostics::{Applicability, CompileError, Diagnostics, ErrorKind, Severity, SourceFile, Suggestion};
+pub use token::{Span, SpannedToken, Token};
+pub use url::UrlPolicy;
+
+/// Settings for a single compilation.
+#[derive(Debug, Clone)]
+pub struct Options {
+    /// Name the source is shown under in diagnostics, usually the path of the .lol file.
+    pub file_name: String,
+    /// Which urls links, images, audio, video, quote cites and the head's stylesheets, scripts and icon may use,
+    /// http, https and relative urls by default.
+    pub urls: UrlPolicy,
+    /// Language of the page, written to the `lang` attribute of `<html>`.
+    pub lang: String,
+    /// Put block level elements on their own lines with indentation, instead of writing the whole page on one line.
+    /// Inline content (audio and video included) stays on the line its on, so the page renders the same either way.
+    pub pretty: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self { file_name: "<input>".to_string(), urls: UrlPolicy::default(), lang: "en".to_string(), pretty: false }
+    }
+}
+
+/// Everything produced by a successful compilation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Output {
+    /// The generated html document.
+    pub html: String,
+    /// Anything that looked wrong but didnt stop compilation, printable the same way as a failed compile's diagnostics.
+    pub warnings: Diagnostics,
+}
+
+/// Compiles lolcode source into html.
+/// On failure the returned `Diagnostics` can be printed as is, it already knows the file name and source text.
+pub fn compile_str(source: &str, options: &Options) -> Result<Output, Diagnostics> {
+    let mut compiler = LolcodeCompiler::with_options(options);
+    match compiler.compile(source) {
+        Ok(html) => {
+            let warnings = compiler.warnings().to_vec();
+            Ok(Output { html, warnings: Diagnostics::new(SourceFile::new(&options.file_name, source), warnings) })
+        }
+        Err(errors) => Err(Diagnostics::new(SourceFile::new(&options.file_name, source), errors)),
+    }
+}
@@ -0,0 +1,835 @@
+//! Syntax analysis: checks the token stream against the lolcode grammar and builds the syntax tree.
+
+use crate::ast::{
+    Cell, CodeBlock, Document, Head, HeadItem, Heading, Image, Inline, Link, List, ListItem, ListKind, Media, Meta, MetaName, Node, Paragraph, Quote,
+    Row, Section, SectionKind, Setting, Style, StyleBlock, Table, VariableDefine, VariableUse,
+};
+use crate::diagnostics::{CompileError, ErrorKind};
+use crate::keywords::{self, KeywordKind};
+use crate::token::{Span, SpannedToken, Token};
+
+//how many lists, tables, sections, quotes, links and text styles can go inside each other (counted together, so a list in a bold in a section is 3 deep)
+//every level is a few calls deep here and in semantic analysis, so without a limit a deep enough document overflows the stack and takes the whole process down with it
+const MAX_DEPTH: usize = 256;
+
+/// Builds the syntax tree for a whole document out of its tokens.
+pub trait SyntaxAnalyzer {
+/// Adds a token to the end of `token_vector`.
+fn grab_token(&mut self, token: SpannedToken);
+/// `#HAI` to `#KTHXBYE`, the whole document.
+fn parse_lolcode(&mut self) -> Document;
+}
+
+/// Syntax analyzer for lolcode.
+/// Fill `token_vector` with the lexer's tokens (in source order), then call `parse_lolcode` to get the `Document`.
+/// The document is always produced, check `errors` afterwards to see if it was actually valid.
+pub struct LolcodeSyntaxAnalyzer{
+    /// The tokens to parse, in source order.
+    pub token_vector: Vec<SpannedToken>,
+    /// The token being looked at right now.
+    pub current_token : SpannedToken,
+    /// Every syntax error found so far.
+    pub errors: Vec<CompileError>,
+    //the kinds of section currently being parsed, innermost last, so a header inside a footer (or the like) can be caught
+    sections: Vec<SectionKind>,
+    //how many of the constructs counted against MAX_DEPTH are open right now
+    depth: usize,
+}
+
+impl LolcodeSyntaxAnalyzer{
+    /// Creates a syntax analyzer with no tokens.
+    pub fn new() -> Self{
+        Self {
+            token_vector: Vec::new(),
+            current_token : SpannedToken { token: Token::Eof, span: Span::default() },
+            errors: Vec::new(),
+            sections: Vec::new(),
+            depth: 0,
+        }
+    }
+    fn at(&self, token: Token) -> bool {
+        self.current_token.token == token
+    }
+    fn at_text(&self) -> bool {
+        self.current_token.token.is_text()
+    }
+    //builds a syntax error pointing at the current token
+    fn error(&self, message: &str) -> CompileError {
+        CompileError::new(ErrorKind::Syntax, message, self.current_token.span)
+    }
+    fn expected(&self, what: &str) -> CompileError {
+        self.error(&format!("expected {} but found {} instead", what, self.describe_current()))
+    }
+    //records the error, then skips ahead to somewhere it makes sense to start parsing again
+    //closer is whatever ends the construct were currently inside of (#OIC for paragraphs, lists and the head), its left alone so that construct can finish normally
+    //any other #MKAY, #OIC or #TLDR is assumed to close the construct that just broke, so its eaten, and a block level keyword means something new is starting
+    fn recover(&mut self, error: CompileError, closer: Option<Token>) {
+        self.errors.push(error);
+        loop {
+            let token = &self.current_token.token;
+            if matches!(token, Token::Eof | Token::Kthxbye) || closer.as_ref() == Some(token) || token.starts_block() {
+                return;
+            }
+            let closes = token.is_closer();
+            self.next_token();
+            if closes {
+                return;
+            }
+        }
+    }
+    //called on the keyword that starts something that can nest, if thats already MAX_DEPTH deep it gets reported and false is returned
+    //the whole construct is skipped in that case, keywords that open something and the ones that close it are counted so it stops right after the matching closer
+    //whenever this returns true the caller has to take the depth back down once its done
+    fn nest(&mut self) -> bool {
+        if self.depth < MAX_DEPTH {
+            self.depth += 1;
+            return true;
+        }
+        let message = format!("{} is nested too deep, things can only go {} levels inside each other", self.current_token.token, MAX_DEPTH);
+        self.errors.push(self.error(&message));
+        let mut open = 0usize;
+        while !matches!(self.current_token.token, Token::Eof | Token::Kthxbye) {
+            match keywords::by_token(&self.current_token.token).map(|keyword| keyword.kind) {
+                Some(KeywordKind::Opener) => open += 1,
+                Some(KeywordKind::Closer) => open = open.saturating_sub(1),
+                _ => {}
+            }
+            self.next_token();
+            if open == 0 {
+                break;
+            }
+        }
+        false
+    }
+    //one piece of anything that can go inside bold or italics, None if the current token cant start one
+    //list items and styles both go through here, keeping it out of the functions that recurse keeps their stack frames small
+    fn parse_inline(&mut self) -> Result<Option<Inline>, CompileError> {
+        //no ? in each arm, without optimizations every one of those gets its own copy of the result on the stack
+        let inline = match self.current_token.token {
+            Token::Text(_) => self.parse_text().map(Inline::Text),
+            _ if style(&self.current_token.token).is_some() => self.parse_styled(),
+            Token::LemmeSee => self.parse_variable_use().map(Inline::Variable),
+            Token::GimmehNewline => self.parse_newline(),
+            Token::GimmehHtml => self.parse_raw(),
+            Token::GimmehLink => self.parse_link(),
+            Token::GimmehPikchur => self.parse_image(),
+            _ => return Ok(None),
+        };
+        inline.map(Some)
+    }
+    //text tokens get quoted (and trimmed) so its obvious where they start and end in the message
+    fn describe_current(&self) -> String {
+        match &self.current_token.token {
+            Token::Text(text) => format!("text '{}'", text.trim()),
+            Token::Verbatim(_) => "a code or style block".to_string(),
+            other => other.to_string(),
+        }
+    }
+    //a text token followed by whatever closes the construct, this is the shape of most of the simple constructs (title, bold, soundz, etc)
+    //hands back the text along with where it started
+    fn parse_enclosed(&mut self, closer: Token) -> Result<(String, Span), CompileError> {
+        let span = self.current_token.span;
+        if !self.at_text() {
+            return Err(self.expected("text"));
+        }
+        let text = self.parse_text()?;
+        if !self.at(closer.clone()) {
+            return Err(self.expected(closer.lexeme()));
+        }
+        self.next_token();
+        Ok((text, span))
+    }
+    //variable names have to be one word, so this checks to see if theres any spaces in it, and if there is, gives an error
+    fn parse_variable_name(&mut self) -> Result<(String, Span), CompileError> {
+        let name = match &self.current_token.token {
+            Token::Text(name) => name.trim().to_string(),
+            _ => return Err(self.expected("text")),
+        };
+        if name.contains(' ') {
+            return Err(self.error(&format!("{} is an invalid variable name", name)));
+        }
+        let span = self.current_token.span;
+        self.next_token();
+        Ok((name, span))
+    }
+}
+
+impl Default for LolcodeSyntaxAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SyntaxAnalyzer for LolcodeSyntaxAnalyzer{
+
+    //this is the function that grabs the tokens from the parse method in lexical analysis, it then stores the tokens to a different token vector for usage in syntax analysis
+    fn grab_token(&mut self, token: SpannedToken){
+        self.token_vector.push(token);
+    }
+    //this function is basically the driver for syntax analysis, this is where it both starts and ends
+    //after everything is analyzed, the finished document is handed back for semantic analysis, any errors found along the way are sitting in self.errors
+    //regarding the specifics of syntax analysis, most of it is pretty straightforward but i did write some commentary for the more interesting parts of it
+    fn parse_lolcode(&mut self) -> Document{
+        self.token_vector.reverse();
+        self.next_token();
+        if self.at(Token::Hai) {
+            self.next_token();
+        } else {
+            self.errors.push(self.expected("#HAI"));
+        }
+        let mut comments = Vec::new();
+        while self.at(Token::Obtw) {
+            if let Err(error) = self.parse_comment(&mut comments) {
+                self.recover(error, None);
+            }
+        }
+        let head = self.parse_head();
+        let mut body = Vec::new();
+        self.parse_body(&mut body);
+        if self.at(Token::Kthxbye) {
+            self.next_token();
+            if !self.at(Token::Eof) {
+                self.errors.push(self.error("extra tokens found after #KTHXBYE"));
+            }
+        } else {
+            self.errors.push(self.expected("#KTHXBYE"));
+        }
+        Document { comments, head, body }
+    }
+}
+
+//recursive descent over the lolcode grammar, one method per nonterminal
+//each parse_* method expects the current token to be the first token of its construct
+//repetition (body content, paragraph content, comments, list items) is handled with loops rather than recursion, so long documents dont grow the stack
+//methods that match a whole run of constructs push them onto the vector they are given, the rest return what they parsed
+//the methods that dont return a Result recover from errors themselves, recording them and carrying on from the next sync point
+impl LolcodeSyntaxAnalyzer{
+    //method that quickly grabs the next token from the token vector (made it so i didnt have to write out that line a bunch)
+    //if the vector is already empty the current token just stays as the end of file token
+    fn next_token(&mut self){
+        if let Some(token) = self.token_vector.pop() {
+            self.current_token = token;
+        }
+    }
+    //the title and everything else in the head can come in any order, the head still needs a title though, so not finding one by the #OIC is an error
+    //a second title parses just fine, its semantic analysis that complains about it
+    //running into #KTHXBYE or anything block level means the #OIC went missing, so the head ends there
+    fn parse_head(&mut self) -> Option<Head>{
+        if !self.at(Token::MaekHead) {
+            return None;
+        }
+        self.next_token();
+        let mut items = Vec::new();
+        let mut titled = false;
+        loop {
+            let item = match self.current_token.token {
+                Token::Oic => {
+                    if !titled {
+                        self.errors.push(self.expected("#GIMMEH TITLE"));
+                    }
+                    self.next_token();
+                    break;
+                }
+                Token::GimmehTitle => {
+                    titled = true;
+                    self.parse_title()
+                }
+                Token::GimmehDescripshun | Token::GimmehAuthor | Token::GimmehKeywordz | Token::GimmehViewport => self.parse_meta(),
+                Token::GimmehStylesheet | Token::GimmehScript | Token::GimmehIcon => self.parse_head_link(),
+                Token::MaekStyle => self.parse_style(),
+                _ => {
+                    let token = &self.current_token.token;
+                    if matches!(token, Token::Kthxbye | Token::Eof) || token.starts_block() {
+                        self.errors.push(self.expected("#OIC"));
+                        break;
+                    }
+                    self.errors.push(self.expected("#GIMMEH TITLE or another head tag"));
+                    self.next_token();
+                    continue;
+                }
+            };
+            match item {
+                Ok(item) => items.push(item),
+                Err(error) => self.recover(error, Some(Token::Oic)),
+            }
+        }
+        Some(Head { items })
+    }
+    fn parse_title(&mut self) -> Result<HeadItem, CompileError>{
+        self.next_token();
+        let (value, span) = self.parse_enclosed(Token::Mkay)?;
+        Ok(HeadItem::Title(Setting { value, span }))
+    }
+    fn parse_meta(&mut self) -> Result<HeadItem, CompileError>{
+        let name = match self.current_token.token {
+            Token::GimmehAuthor => MetaName::Author,
+            Token::GimmehKeywordz => MetaName::Keywords,
+            Token::GimmehViewport => MetaName::Viewport,
+            _ => MetaName::Description,
+        };
+        self.next_token();
+        let (content, _) = self.parse_enclosed(Token::Mkay)?;
+        Ok(HeadItem::Meta(Meta { name, content: content.trim().to_string() }))
+    }
+    //stylesheets, scripts and the icon are all just a url, the keyword says what its for
+    fn parse_head_link(&mut self) -> Result<HeadItem, CompileError>{
+        let keyword = self.current_token.token.clone();
+        self.next_token();
+        let (src, span) = self.parse_enclosed(Token::Mkay)?;
+        let media = Media { src, span };
+        Ok(match keyword {
+            Token::GimmehScript => HeadItem::Script(media),
+            Token::GimmehIcon => HeadItem::Icon(media),
+            _ => HeadItem::Stylesheet(media),
+        })
+    }
+    //same as a code block, except theres no language, so the css has to start on the line after #MAEK STYLE
+    fn parse_style(&mut self) -> Result<HeadItem, CompileError>{
+        self.next_token();
+        if self.at_text() {
+            return Err(self.error("the css goes on the lines after #MAEK STYLE, not on the same line"));
+        }
+        let span = self.current_token.span;
+        let css = match &self.current_token.token {
+            Token::Verbatim(css) => css.clone(),
+            _ => return Err(self.expected("a style block")),
+        };
+        self.next_token();
+        if !self.at(Token::KthxStyle) {
+            return Err(self.expected("#KTHX STYLE"));
+        }
+        self.next_token();
+        Ok(HeadItem::Style(StyleBlock { css, span }))
+    }
+    //since comments can be repeated over and over again before the body, the method keeps going until #OBTW isnt found again
+    fn parse_comment(&mut self, comments: &mut Vec<String>) -> Result<(), CompileError>{
+        while self.at(Token::Obtw) {
+            self.next_token();
+            let (comment, _) = self.parse_enclosed(Token::Tldr)?;
+            comments.push(comment);
+        }
+        Ok(())
+    }
+    //basically just keeps looking for tokens until #KTHXBYE is found since its always the last token in the body
+    fn parse_body(&mut self, body: &mut Vec<Node>){
+        while !matches!(self.current_token.token, Token::Kthxbye | Token::Eof) {
+            self.parse_inner_body(body, None);
+        }
+    }
+    //one piece of body content, sections and quotes use this for their content too, closer is whatever ends the one being parsed
+    //if something inside goes wrong the error gets recorded and parsing picks back up at the next sync point, a token that cant start anything is just reported and skipped
+    fn parse_inner_body(&mut self, body: &mut Vec<Node>, closer: Option<Token>){
+        let result = match self.current_token.token {
+            Token::Text(_) => self.parse_text().map(|text| Node::Inline(Inline::Text(text))),
+            _ if style(&self.current_token.token).is_some() => self.parse_styled().map(Node::Inline),
+            Token::GimmehNewline => self.parse_newline().map(Node::Inline),
+            Token::GimmehHtml => self.parse_raw().map(Node::Inline),
+            Token::GimmehLink => self.parse_link().map(Node::Inline),
+            Token::GimmehPikchur => self.parse_image().map(Node::Inline),
+            Token::MaekList | Token::MaekNumberz => Ok(self.parse_list()),
+            Token::MaekTable => Ok(self.parse_table()),
+            Token::MaekCode => self.parse_code(),
+            Token::GimmehSoundz => self.parse_audio(),
+            Token::GimmehVidz => self.parse_video(),
+            Token::GimmehHeadr => self.parse_heading(),
+            Token::IHaz => match self.parse_variable_define() {
+                Ok(Some(variable)) => Ok(Node::Define(variable)),
+                Ok(None) => unreachable!("#I HAZ always starts a variable definition"),
+                Err(error) => Err(error),
+            },
+            Token::LemmeSee => self.parse_variable_use().map(|variable| Node::Inline(Inline::Variable(variable))),
+            Token::MaekParagraf => Ok(self.parse_paragraph()),
+            Token::MaekSection | Token::MaekArticle | Token::MaekHeader | Token::MaekFooter | Token::MaekNav | Token::MaekDiv => Ok(self.parse_section()),
+            Token::MaekQuote => Ok(self.parse_quote()),
+            Token::GimmehRule => self.parse_rule(),
+            Token::Obtw => {
+                let mut comments = Vec::new();
+                let result = self.parse_comment(&mut comments);
+                body.extend(comments.into_iter().map(Node::Comment));
+                if let Err(error) = result {
+                    self.recover(error, closer);
+                }
+                return;
+            }
+            _ => {
+                self.errors.push(self.expected("valid body token"));
+                self.next_token();
+                return;
+            }
+        };
+        match result {
+            Ok(node) => body.push(node),
+            Err(error) => self.recover(error, closer),
+        }
+    }
+    //header and footer are the only ones with a rule about what goes inside them, they cant hold another header or footer at any depth
+    //thats reported but the section is still parsed like normal, theres nothing wrong with the tokens themselves
+    fn parse_section(&mut self) -> Node{
+        let keyword = self.current_token.token.clone();
+        let kind = match keyword {
+            Token::MaekArticle => SectionKind::Article,
+            Token::MaekHeader => SectionKind::Header,
+            Token::MaekFooter => SectionKind::Footer,
+            Token::MaekNav => SectionKind::Nav,
+            Token::MaekDiv => SectionKind::Div,
+            _ => SectionKind::Section,
+        };
+        let is_edge = |kind: &SectionKind| matches!(kind, SectionKind::Header | SectionKind::Footer);
+        if is_edge(&kind) && let Some(outer) = self.sections.iter().rev().find(|outer| is_edge(outer)) {
+            let outer = if *outer == SectionKind::Header { Token::MaekHeader } else { Token::MaekFooter };
+            self.errors.push(self.error(&format!("{} cant go inside a {}", keyword, outer)));
+        }
+        let mut content = Vec::new();
+        if !self.nest() {
+            return Node::Section(Section { kind, content });
+        }
+        self.next_token();
+        self.sections.push(kind);
+        self.parse_inner_section(&mut content);
+        self.sections.pop();
+        self.depth -= 1;
+        Node::Section(Section { kind, content })
+    }
+    //the cite is optional, same as a paragraph's variable, but if its there it has to come first
+    fn parse_quote(&mut self) -> Node{
+        let mut cite = None;
+        let mut content = Vec::new();
+        if !self.nest() {
+            return Node::Quote(Quote { cite, content });
+        }
+        self.next_token();
+        if self.at(Token::From) {
+            self.next_token();
+            match self.parse_enclosed(Token::Mkay) {
+                Ok((src, span)) => cite = Some(Media { src, span }),
+                Err(error) => self.recover(error, Some(Token::Oic)),
+            }
+        }
+        self.parse_inner_section(&mut content);
+        self.depth -= 1;
+        Node::Quote(Quote { cite, content })
+    }
+    //works like parse_inner_paragraph except everything the body can hold is allowed, paragraphs and other sections included
+    fn parse_inner_section(&mut self, content: &mut Vec<Node>){
+        loop {
+            match self.current_token.token {
+                Token::Oic => {
+                    self.next_token();
+                    return;
+                }
+                Token::Kthxbye | Token::Eof | Token::MaekHead => {
+                    self.errors.push(self.expected("#OIC"));
+                    return;
+                }
+                _ => self.parse_inner_body(content, Some(Token::Oic)),
+            }
+        }
+    }
+    fn parse_rule(&mut self) -> Result<Node, CompileError>{
+        self.next_token();
+        Ok(Node::Rule)
+    }
+    //the variable definition is optional, but if its there it has to come first
+    fn parse_paragraph(&mut self) -> Node{
+        self.next_token();
+        let variable = match self.parse_variable_define() {
+            Ok(variable) => variable,
+            Err(error) => {
+                self.recover(error, Some(Token::Oic));
+                None
+            }
+        };
+        let mut content = Vec::new();
+        self.parse_inner_paragraph(&mut content);
+        Node::Paragraph(Paragraph { variable, content })
+    }
+    //keeps handing pieces of the paragraph to parse_inner_text until #OIC shows up
+    //running into something that can only end or restart the body (#KTHXBYE, another paragraph or a section) means the #OIC went missing, so the paragraph just ends there
+    fn parse_inner_paragraph(&mut self, content: &mut Vec<Node>){
+        loop {
+            match self.current_token.token {
+                Token::Oic => {
+                    self.next_token();
+                    return;
+                }
+                Token::Kthxbye | Token::Eof | Token::MaekParagraf | Token::MaekHead | Token::MaekSection | Token::MaekArticle | Token::MaekHeader
+                | Token::MaekFooter | Token::MaekNav | Token::MaekDiv | Token::MaekQuote => {
+                    self.errors.push(self.expected("#OIC"));
+                    return;
+                }
+                _ => self.parse_inner_text(content),
+            }
+        }
+    }
+    //basically the same logic as the body parsing, just one piece of paragraph content at a time
+    fn parse_inner_text(&mut self, content: &mut Vec<Node>){
+        let result = match self.current_token.token {
+            Token::Text(_) => self.parse_text().map(|text| Node::Inline(Inline::Text(text))),
+            Token::LemmeSee => self.parse_variable_use().map(|variable| Node::Inline(Inline::Variable(variable))),
+            _ if style(&self.current_token.token).is_some() => self.parse_styled().map(Node::Inline),
+            Token::GimmehNewline => self.parse_newline().map(Node::Inline),
+            Token::GimmehHtml => self.parse_raw().map(Node::Inline),
+            Token::GimmehLink => self.parse_link().map(Node::Inline),
+            Token::GimmehPikchur => self.parse_image().map(Node::Inline),
+            Token::GimmehSoundz => self.parse_audio(),
+            Token::GimmehVidz => self.parse_video(),
+            Token::MaekList | Token::MaekNumberz => Ok(self.parse_list()),
+            Token::MaekTable => Ok(self.parse_table()),
+            Token::MaekCode => self.parse_code(),
+            _ => {
+                self.errors.push(self.expected("valid paragraph body token"));
+                self.next_token();
+                return;
+            }
+        };
+        match result {
+            Ok(node) => content.push(node),
+            Err(error) => self.recover(error, Some(Token::Oic)),
+        }
+    }
+    //relatively simple method besides when it checks to see if the variable name is valid (thats in parse_variable_name)
+    fn parse_variable_define(&mut self) -> Result<Option<VariableDefine>, CompileError>{
+        if !self.at(Token::IHaz) {
+            return Ok(None);
+        }
+        self.next_token();
+        let (name, span) = self.parse_variable_name()?;
+        if !self.at(Token::ItIz) {
+            return Err(self.expected("#IT IZ"));
+        }
+        self.next_token();
+        let (value, _) = self.parse_enclosed(Token::Mkay)?;
+        Ok(Some(VariableDefine { name, value, span }))
+    }
+    fn parse_variable_use(&mut self) -> Result<VariableUse, CompileError>{
+        self.next_token();
+        let (name, span) = self.parse_variable_name()?;
+        if !self.at(Token::Mkay) {
+            return Err(self.expected("#MKAY"));
+        }
+        self.next_token();
+        Ok(VariableUse { name, span })
+    }
+    //bold, italics and the other text styles all work the same way, the keyword just picks the style
+    fn parse_styled(&mut self) -> Result<Inline, CompileError>{
+        let style = match style(&self.current_token.token) {
+            Some(style) => style,
+            None => return Err(self.expected("a text style")),
+        };
+        //the empty text only stands in for what was skipped, theres already an error so it never makes it onto a page
+        if !self.nest() {
+            return Ok(Inline::Text(String::new()));
+        }
+        self.next_token();
+        let content = self.parse_formatted();
+        self.depth -= 1;
+        Ok(Inline::Styled(style, content?))
+    }
+    //whats inside bold, italics or any of the other styles, up to and including the #MKAY that closes it
+    //styles can go inside each other up to MAX_DEPTH deep, each one eats its own #MKAY so they line up from the inside out
+    fn parse_formatted(&mut self) -> Result<Vec<Inline>, CompileError>{
+        let mut content = Vec::new();
+        loop {
+            if self.at(Token::Mkay) {
+                break;
+            }
+            match self.parse_inline()? {
+                Some(inline) => content.push(inline),
+                None => return Err(self.expected("#MKAY")),
+            }
+        }
+        if content.is_empty() {
+            return Err(self.expected("text"));
+        }
+        self.next_token();
+        Ok(content)
+    }
+    //both kinds of list share the item grammar, numbered lists just get to have their settings first
+    fn parse_list(&mut self) -> Node{
+        let kind = if self.at(Token::MaekNumberz) { ListKind::Numbers } else { ListKind::Bullets };
+        let mut list = List { kind, start: None, style: None, items: Vec::new() };
+        if !self.nest() {
+            return Node::List(list);
+        }
+        self.next_token();
+        if let Err(error) = self.parse_list_settings(&mut list) {
+            self.recover(error, Some(Token::Oic));
+        }
+        self.parse_list_items(&mut list.items);
+        self.depth -= 1;
+        Node::List(list)
+    }
+    //each setting can show up once, in any order, whether the value makes any sense is up to semantic analysis
+    //bulleted lists dont have any settings, but giving them one is a mistake worth its own message instead of a pile of "expected #GIMMEH ITEM"s
+    fn parse_list_settings(&mut self, list: &mut List) -> Result<(), CompileError>{
+        loop {
+            let keyword = self.current_token.token.clone();
+            if !matches!(keyword, Token::StartinAt | Token::LookinLiek) {
+                return Ok(());
+            }
+            let keyword_span = self.current_token.span;
+            if list.kind == ListKind::Bullets {
+                return Err(self.error(&format!("only numbered lists (#MAEK NUMBERZ) can have {}", keyword)));
+            }
+            self.next_token();
+            let (value, span) = self.parse_enclosed(Token::Mkay)?;
+            let setting = Some(Setting { value: value.trim().to_string(), span });
+            let slot = if keyword == Token::StartinAt { &mut list.start } else { &mut list.style };
+            if slot.is_some() {
+                return Err(CompileError::new(ErrorKind::Syntax, format!("{} given more than once for the same list", keyword), keyword_span));
+            }
+            *slot = setting;
+        }
+    }
+    //similar setup to parse_comments, basically just keeps going until #OIC is found, it then returns
+    //a broken item is skipped up to its #MKAY, and anything block level showing up means the #OIC went missing so the list ends there
+    fn parse_list_items(&mut self, items: &mut Vec<ListItem>){
+        loop {
+            if self.at(Token::Oic) {
+                self.next_token();
+                return;
+            } else if self.at(Token::GimmehItem) {
+                self.next_token();
+                let item = self.parse_inner_list().and_then(|content| {
+                    if !self.at(Token::Mkay) {
+                        return Err(self.expected("#MKAY"));
+                    }
+                    self.next_token();
+                    Ok(ListItem { content })
+                });
+                match item {
+                    Ok(item) => items.push(item),
+                    Err(error) => self.recover(error, Some(Token::Oic)),
+                }
+            } else {
+                self.errors.push(self.expected("#GIMMEH ITEM"));
+                let token = &self.current_token.token;
+                if matches!(token, Token::Kthxbye | Token::Eof) || token.starts_block() {
+                    return;
+                }
+                self.next_token();
+            }
+        }
+    }
+    //everything in an item up to its #MKAY (which is left for parse_list_items), lists inside items can nest up to MAX_DEPTH deep
+    fn parse_inner_list(&mut self) -> Result<Vec<Node>, CompileError>{
+        let mut content = Vec::new();
+        loop {
+            let node = match self.current_token.token {
+                Token::MaekList | Token::MaekNumberz => self.parse_list(),
+                Token::MaekTable => self.parse_table(),
+                Token::MaekCode => self.parse_code()?,
+                _ => match self.parse_inline()? {
+                    Some(inline) => Node::Inline(inline),
+                    None => return Ok(content),
+                },
+            };
+            content.push(node);
+        }
+    }
+    //the level is the first word of the text right after the keyword, whatever comes after it is the start of the heading itself
+    //past the level a heading holds the same things bold and italics can, so parse_formatted handles the rest (and the #MKAY)
+    fn parse_heading(&mut self) -> Result<Node, CompileError>{
+        self.next_token();
+        let text = match &self.current_token.token {
+            Token::Text(text) => text.trim_start().to_string(),
+            _ => return Err(self.expected("a heading level")),
+        };
+        let level_end = text.find(char::is_whitespace).unwrap_or(text.len());
+        let level = &text[..level_end];
+        //exactly one digit, parse would also take "+2" or "02"
+        let level = match level.as_bytes() {
+            [digit @ b'1'..=b'6'] => digit - b'0',
+            _ => {
+                let span = Span { len: level.len(), ..self.current_token.span };
+                return Err(CompileError::new(ErrorKind::Syntax, format!("heading level has to be a number from 1 to 6, not '{}'", level), span));
+            }
+        };
+        //the whitespace after the level just separates it from the heading, same as the whitespace after a keyword
+        let rest = text[level_end..].trim_start();
+        if rest.trim().is_empty() {
+            self.next_token();
+        } else {
+            self.current_token.token = Token::Text(rest.to_string());
+        }
+        let content = self.parse_formatted()?;
+        Ok(Node::Heading(Heading { level, content }))
+    }
+    fn parse_table(&mut self) -> Node{
+        let mut rows = Vec::new();
+        if !self.nest() {
+            return Node::Table(Table { rows });
+        }
+        self.next_token();
+        self.parse_table_rows(&mut rows);
+        self.depth -= 1;
+        Node::Table(Table { rows })
+    }
+    //works just like parse_list_items, one level up, every row is its own #MAEK ROW ... #OIC
+    //anything block level showing up means the table's #OIC went missing so the table ends there
+    fn parse_table_rows(&mut self, rows: &mut Vec<Row>){
+        loop {
+            if self.at(Token::Oic) {
+                self.next_token();
+                return;
+            } else if self.at(Token::MaekRow) {
+                let span = self.current_token.span;
+                self.next_token();
+                let mut cells = Vec::new();
+                self.parse_table_cells(&mut cells);
+                rows.push(Row { cells, span });
+            } else {
+                self.errors.push(self.expected("#MAEK ROW"));
+                let token = &self.current_token.token;
+                if matches!(token, Token::Kthxbye | Token::Eof) || token.starts_block() {
+                    return;
+                }
+                self.next_token();
+            }
+        }
+    }
+    //the cells of one row up to its #OIC, a cell holds the same things a list item does
+    //a broken cell is skipped up to its #MKAY, and a new row or anything block level means this row's #OIC went missing
+    fn parse_table_cells(&mut self, cells: &mut Vec<Cell>){
+        loop {
+            let header = match self.current_token.token {
+                Token::Oic => {
+                    self.next_token();
+                    return;
+                }
+                Token::GimmehHeaderCell => true,
+                Token::GimmehCell => false,
+                _ => {
+                    self.errors.push(self.expected("#GIMMEH CELL"));
+                    let token = &self.current_token.token;
+                    if matches!(token, Token::Kthxbye | Token::Eof | Token::MaekRow) || token.starts_block() {
+                        return;
+                    }
+                    self.next_token();
+                    continue;
+                }
+            };
+            self.next_token();
+            let cell = self.parse_inner_list().and_then(|content| {
+                if !self.at(Token::Mkay) {
+                    return Err(self.expected("#MKAY"));
+                }
+                self.next_token();
+                Ok(Cell { header, content })
+            });
+            match cell {
+                Ok(cell) => cells.push(cell),
+                Err(error) => self.recover(error, Some(Token::Oic)),
+            }
+        }
+    }
+    //the lexer already did the hard part, this just gets the language (one word, its used as a class name) and the code out of the tokens
+    fn parse_code(&mut self) -> Result<Node, CompileError>{
+        self.next_token();
+        let mut language = None;
+        if let Token::Text(text) = &self.current_token.token {
+            let text = text.trim().to_string();
+            if text.contains(char::is_whitespace) {
+                return Err(self.error(&format!("code block language has to be one word, not '{}'", text)));
+            }
+            language = Some(text);
+            self.next_token();
+        }
+        let code = match &self.current_token.token {
+            Token::Verbatim(code) => code.clone(),
+            _ => return Err(self.expected("a code block")),
+        };
+        self.next_token();
+        if !self.at(Token::KthxCode) {
+            return Err(self.expected("#KTHX CODE"));
+        }
+        self.next_token();
+        Ok(Node::Code(CodeBlock { language, code }))
+    }
+    fn parse_audio(&mut self) -> Result<Node, CompileError>{
+        self.next_token();
+        let (src, span) = self.parse_enclosed(Token::Mkay)?;
+        Ok(Node::Audio(Media { src, span }))
+    }
+    fn parse_video(&mut self) -> Result<Node, CompileError>{
+        self.next_token();
+        let (src, span) = self.parse_enclosed(Token::Mkay)?;
+        Ok(Node::Video(Media { src, span }))
+    }
+    fn parse_newline(&mut self) -> Result<Inline, CompileError>{
+        //this one pretty complicated all things considered
+        self.next_token();
+        Ok(Inline::Newline)
+    }
+    fn parse_raw(&mut self) -> Result<Inline, CompileError>{
+        self.next_token();
+        let (markup, _) = self.parse_enclosed(Token::Mkay)?;
+        Ok(Inline::Raw(markup))
+    }
+    //same shape as a variable definition, the url and then the text after #IT SEZ, which ends at the link's own #MKAY
+    fn parse_link(&mut self) -> Result<Inline, CompileError>{
+        if !self.nest() {
+            return Ok(Inline::Text(String::new()));
+        }
+        self.next_token();
+        let link = self.parse_enclosed(Token::ItSez).and_then(|(href, span)| Ok(Link { href, span, content: self.parse_formatted()? }));
+        self.depth -= 1;
+        link.map(Inline::Link)
+    }
+    //the url comes first, then the size in either order (both optional), then the alt text, then #MKAY
+    //leaving the alt text off isnt a syntax error, the image is fine to parse, semantic analysis is the one that complains about it
+    fn parse_image(&mut self) -> Result<Inline, CompileError>{
+        let span = self.current_token.span;
+        self.next_token();
+        let src_span = self.current_token.span;
+        let src = self.parse_text()?;
+        let mut image = Image { src: Media { src, span: src_span }, alt: None, width: None, height: None, span };
+        while matches!(self.current_token.token, Token::Widf | Token::Hite) {
+            let keyword = self.current_token.token.clone();
+            let keyword_span = self.current_token.span;
+            self.next_token();
+            let value_span = self.current_token.span;
+            let value = self.parse_text()?.trim().to_string();
+            let slot = if keyword == Token::Widf { &mut image.width } else { &mut image.height };
+            if slot.is_some() {
+                return Err(CompileError::new(ErrorKind::Syntax, format!("{} given more than once for the same image", keyword), keyword_span));
+            }
+            *slot = Some(Setting { value, span: value_span });
+        }
+        if self.at(Token::ItSez) {
+            self.next_token();
+            let alt = if self.at_text() { self.parse_text()?.trim().to_string() } else { String::new() };
+            image.alt = Some(alt);
+        }
+        if !self.at(Token::Mkay) {
+            return Err(self.expected("#MKAY"));
+        }
+        self.next_token();
+        Ok(Inline::Image(image))
+    }
+    fn parse_text(&mut self) -> Result<String, CompileError>{
+        //this one was also pretty complicated
+        let text = match &self.current_token.token {
+            Token::Text(text) => text.clone(),
+            _ => return Err(self.expected("text")),
+        };
+        self.next_token();
+        Ok(text)
+    }
+}
+
+//the text style a keyword starts, if it starts one
+fn style(token: &Token) -> Option<Style> {
+    match token {
+        Token::GimmehBold => Some(Style::Bold),
+        Token::GimmehItalics => Some(Style::Italics),
+        Token::GimmehUnderline => Some(Style::Underline),
+        Token::GimmehStrikethru => Some(Style::Strikethrough),
+        Token::GimmehCode => Some(Style::Code),
+        Token::GimmehSubscript => Some(Style::Subscript),
+        Token::GimmehSuperscript => Some(Style::Superscript),
+        Token::GimmehHighlight => Some(Style::Highlight),
+        Token::GimmehSmol => Some(Style::Small),
+        _ => None,
+    }
+}
@@ -0,0 +1,610 @@
+//! Semantic analysis: resolves variables and turns the syntax tree into html.
+//!
+//! Everything that came from the source is escaped before it is written out, text for element content
+//! and the stricter attribute escaping for urls. `#GIMMEH HTML` is the only way to get markup through untouched.
+//!
+//! With `Options::pretty` set, block level elements each get their own line and are indented by how deep they are.
+//! Inline content is written exactly as it would be otherwise, so the page renders the same either way.
+//! That includes `<audio>` and `<iframe>`: they sit in a line of text like images do, so a line break around them
+//! would show up as a space on the page, and they stay where they are instead of getting lines of their own.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::ast::{Document, Head, HeadItem, Image, Inline, List, ListKind, MetaName, Node, SectionKind, Setting, Style, Table, VariableUse};
+use crate::diagnostics::{CompileError, ErrorKind};
+use crate::keywords::html_tag;
+use crate::token::{Span, Token};
+use crate::url::UrlPolicy;
+use crate::Options;
+
+/// Semantic analyzer for lolcode.
+/// Call `semantic_analysis` with the `Document` from `LolcodeSyntaxAnalyzer`, then read the html out of `output`.
+/// Variables that arent in scope end up in `errors`, the html is only usable if thats empty.
+pub struct LolcodeSemanticAnalyzer{
+    /// The generated html.
+    pub output: String,
+    /// Every scope and url error found so far.
+    pub errors: Vec<CompileError>,
+    /// Things that still compile but are probably mistakes, these dont make the html unusable.
+    pub warnings: Vec<CompileError>,
+    body_var: HashMap<String, String>,
+    paragraph_var: HashMap<String, String>,
+    paragraph_scope: bool,
+    //links cant go inside other links in html, so this is how a nested one gets caught
+    in_link: bool,
+    url_policy: UrlPolicy,
+    lang: String,
+    //only used to title a page that doesnt have a head
+    file_name: String,
+    pretty: bool,
+    //one entry per block element thats currently open, saying whether any other blocks have been put inside it yet
+    block_children: Vec<bool>,
+    //whether the last thing written was the end of a block, inline content after that starts a new line in pretty mode
+    after_block: bool,
+}
+
+impl LolcodeSemanticAnalyzer{
+    /// Creates a semantic analyzer with the default `Options`.
+    pub fn new() -> Self{
+        Self {
+            output: String::new(),
+            errors: Vec::new(),
+            warnings: Vec::new(),
+            body_var: HashMap::new(),
+            paragraph_var: HashMap::new(),
+            paragraph_scope: false,
+            in_link: false,
+            url_policy: UrlPolicy::default(),
+            lang: "en".to_string(),
+            file_name: Options::default().file_name,
+            pretty: false,
+            block_children: Vec::new(),
+            after_block: false,
+        }
+    }
+    /// Creates a semantic analyzer that uses the given `Options`.
+    pub fn with_options(options: &Options) -> Self{
+        Self {
+            url_policy: options.urls.clone(),
+            lang: options.lang.clone(),
+            file_name: options.file_name.clone(),
+            pretty: options.pretty,
+            ..Self::new()
+        }
+    }
+    //the head's own title, or the stand in for a page that doesnt have a head
+    fn write_title(&mut self, title: &str){
+        let tag = html_tag(&Token::GimmehTitle);
+        self.open_block(&format!("<{}>", tag));
+        self.push_inline(&escape_text(title));
+        self.close_block(&format!("</{}>", tag));
+    }
+    //starts a fresh line indented to however many blocks are open
+    //whitespace already at the end of the output is swapped for the line break rather than kept in front of it, so text ending in a newline doesnt leave blank lines
+    fn new_line(&mut self){
+        let end = self.output.trim_end().len();
+        self.output.truncate(end);
+        let indent = "  ".repeat(self.block_children.len());
+        self.push_output("\n");
+        self.push_output(&indent);
+    }
+}
+
+impl Default for LolcodeSemanticAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Walks a finished syntax tree, checking variable scoping and generating html along the way.
+pub trait SemanticAnalyzer{
+    /// Run the whole pass, every variable that isnt in scope is recorded and skipped.
+    fn semantic_analysis(&mut self, document: &Document);
+}
+
+impl SemanticAnalyzer for LolcodeSemanticAnalyzer{
+    //the head and comments always come out in the same spot, so this just does them in order and then hands the body off to analyze_node
+    //the page is a full html5 document no matter what the .lol file has in it, doctype, lang, a head with the charset and a title (even when theres no #MAEK HEAD) and a body around the content
+    //the main meat of the semantic analysis is the static scoped variables, which i handled using 2 hashmaps, one for the body scope and one for the paragraph scope
+    //when a variable is declared, the corresponding information is added to either the body or paragraph hashmap depending on which one the program is currently in (tracked w/ paragraph_scope bool)
+    //when a paragraph ends, the paragraph hashmap is completely cleared out since that information will never be accessed again and will probably be replaced anyways
+    fn semantic_analysis(&mut self, document: &Document){
+        let html = html_tag(&Token::Hai);
+        self.push_output("<!DOCTYPE html>");
+        self.open_block(&format!("<{} lang=\"{}\">", html, escape_attribute(&self.lang)));
+        for comment in &document.comments {
+            self.analyze_node(&Node::Comment(comment.clone()));
+        }
+        let head_tag = html_tag(&Token::MaekHead);
+        self.open_block(&format!("<{}>", head_tag));
+        self.open_block("<meta charset=\"utf-8\">");
+        self.close_block("");
+        match &document.head {
+            Some(head) => self.analyze_head(head),
+            //html5 needs a title, so a page without a head is named after its file (which is what a browser shows for an untitled page anyway)
+            None => {
+                let name = Path::new(&self.file_name).file_stem().map(|stem| stem.to_string_lossy().into_owned());
+                self.write_title(&name.unwrap_or_else(|| self.file_name.clone()));
+            }
+        }
+        self.close_block(&format!("</{}>", head_tag));
+        self.open_block("<body>");
+        for node in &document.body {
+            self.analyze_node(node);
+        }
+        self.close_block("</body>");
+        self.close_block(&format!("</{}>", html));
+        if self.pretty {
+            self.push_output("\n");
+        }
+    }
+}
+
+impl LolcodeSemanticAnalyzer{
+    fn push_output(&mut self, text: &str){
+        self.output.push_str(text);
+    }
+    //text, or an inline element's tags, in pretty mode anything right after the end of a block starts a new line
+    fn push_inline(&mut self, text: &str){
+        if self.pretty && self.after_block {
+            self.new_line();
+        }
+        self.after_block = false;
+        self.push_output(text);
+    }
+    //in pretty mode a block always starts on its own line, and marks whatever block its inside of as having blocks in it
+    fn open_block(&mut self, markup: &str){
+        if self.pretty {
+            if let Some(has_blocks) = self.block_children.last_mut() {
+                *has_blocks = true;
+            }
+            if !self.output.is_empty() {
+                self.new_line();
+            }
+            self.block_children.push(false);
+        }
+        self.push_output(markup);
+        self.after_block = false;
+    }
+    //the closing tag only gets a line of its own if there were other blocks inside, otherwise it goes right after the content (<li>Homer</li>)
+    fn close_block(&mut self, markup: &str){
+        if self.pretty && self.block_children.pop() == Some(true) {
+            self.new_line();
+        }
+        self.push_output(markup);
+        self.after_block = true;
+    }
+    //element names all come from the keyword registry, so renaming one only has to happen there
+    fn open_tag(&mut self, keyword: &Token){
+        self.push_inline(&format!("<{}>", html_tag(keyword)));
+    }
+    fn close_tag(&mut self, keyword: &Token){
+        self.push_inline(&format!("</{}>", html_tag(keyword)));
+    }
+    //a page only gets one title, so any after the first are errors (and left out)
+    //urls go through the same checks as everywhere else, one that fails is reported and its tag is left out
+    fn analyze_head(&mut self, head: &Head){
+        let mut titled = false;
+        for item in &head.items {
+            match item {
+                HeadItem::Title(title) => {
+                    if titled {
+                        self.errors.push(CompileError::new(ErrorKind::Semantic, "the page already has a title, only one #GIMMEH TITLE is allowed", title.span));
+                        continue;
+                    }
+                    titled = true;
+                    self.write_title(&title.value);
+                }
+                HeadItem::Meta(meta) => {
+                    let tag = html_tag(&Token::GimmehDescripshun);
+                    self.open_block(&format!("<{} name=\"{}\" content=\"{}\">", tag, meta_name(meta.name), escape_attribute(&meta.content)));
+                    self.close_block("");
+                }
+                HeadItem::Stylesheet(media) | HeadItem::Icon(media) => match self.check_url(&media.src, media.span) {
+                    Ok(href) => {
+                        let rel = if matches!(item, HeadItem::Icon(_)) { "icon" } else { "stylesheet" };
+                        self.open_block(&format!("<{} rel=\"{}\" href=\"{}\">", html_tag(&Token::GimmehStylesheet), rel, escape_attribute(&href)));
+                        self.close_block("");
+                    }
+                    Err(error) => self.errors.push(error),
+                },
+                HeadItem::Script(media) => match self.check_url(&media.src, media.span) {
+                    Ok(src) => {
+                        let tag = html_tag(&Token::GimmehScript);
+                        self.open_block(&format!("<{} src=\"{}\">", tag, escape_attribute(&src)));
+                        self.close_block(&format!("</{}>", tag));
+                    }
+                    Err(error) => self.errors.push(error),
+                },
+                //nothing inside a <style> gets escaped by the browser, so the css goes out as is, the only thing it cant have is the end tag
+                HeadItem::Style(style) => {
+                    if style.css.to_ascii_lowercase().contains("</style") {
+                        self.errors.push(CompileError::new(ErrorKind::Semantic, "a style block cant have </style> in it, that would end it early", style.span));
+                        continue;
+                    }
+                    let tag = html_tag(&Token::MaekStyle);
+                    self.open_block(&format!("<{}>", tag));
+                    self.push_output(&style.css);
+                    self.close_block(&format!("</{}>", tag));
+                }
+            }
+        }
+    }
+    //basically just a very lengthy match, whatever kind of node it is, it outputs the corresponding html
+    fn analyze_node(&mut self, node: &Node){
+        match node {
+            Node::Paragraph(paragraph) => {
+                self.paragraph_scope = true;
+                if let Some(variable) = &paragraph.variable {
+                    self.paragraph_var.insert(variable.name.clone(), variable.value.clone());
+                }
+                //html5 doesnt allow things like lists inside a <p> (browsers end the paragraph right before them), so the paragraph gets closed before them and opened again after
+                //the <p> is only opened once theres something to put in it, a paragraph with nothing in it at all still comes out as <p></p> though
+                let tag = html_tag(&Token::MaekParagraf);
+                let mut open = paragraph.content.iter().all(|child| matches!(child, Node::Define(_)));
+                if open {
+                    self.open_block(&format!("<{}>", tag));
+                }
+                for child in &paragraph.content {
+                    let flow = is_flow_only(child);
+                    if flow && open {
+                        self.close_block(&format!("</{}>", tag));
+                        open = false;
+                    } else if !flow && !open && !matches!(child, Node::Define(_)) {
+                        self.open_block(&format!("<{}>", tag));
+                        open = true;
+                    }
+                    self.analyze_node(child);
+                }
+                if open {
+                    self.close_block(&format!("</{}>", tag));
+                }
+                self.paragraph_scope = false;
+                self.paragraph_var.clear();
+            }
+            Node::List(list) => {
+                let tag = html_tag(if list.kind == ListKind::Numbers { &Token::MaekNumberz } else { &Token::MaekList });
+                let attributes = self.list_attributes(list);
+                self.open_block(&format!("<{}{}>", tag, attributes));
+                for item in &list.items {
+                    self.open_block(&format!("<{}>", html_tag(&Token::GimmehItem)));
+                    for child in &item.content {
+                        self.analyze_node(child);
+                    }
+                    self.close_block(&format!("</{}>", html_tag(&Token::GimmehItem)));
+                }
+                self.close_block(&format!("</{}>", tag));
+            }
+            Node::Table(table) => self.analyze_table(table),
+            Node::Section(section) => {
+                let tag = html_tag(&section_keyword(section.kind));
+                self.open_block(&format!("<{}>", tag));
+                for child in &section.content {
+                    self.analyze_node(child);
+                }
+                self.close_block(&format!("</{}>", tag));
+            }
+            //a bad cite is reported and left off, the quote itself is still fine
+            Node::Quote(quote) => {
+                let mut attributes = String::new();
+                if let Some(cite) = &quote.cite {
+                    match self.check_url(&cite.src, cite.span) {
+                        Ok(url) => attributes = format!(" cite=\"{}\"", escape_attribute(&url)),
+                        Err(error) => self.errors.push(error),
+                    }
+                }
+                let tag = html_tag(&Token::MaekQuote);
+                self.open_block(&format!("<{}{}>", tag, attributes));
+                for child in &quote.content {
+                    self.analyze_node(child);
+                }
+                self.close_block(&format!("</{}>", tag));
+            }
+            Node::Rule => {
+                self.open_block(&format!("<{}>", html_tag(&Token::GimmehRule)));
+                self.close_block("");
+            }
+            //the code goes out with nothing but escaping done to it, and push_output instead of push_inline so pretty mode cant touch it either
+            Node::Code(block) => {
+                let class = match &block.language {
+                    Some(language) => format!(" class=\"language-{}\"", escape_attribute(language)),
+                    None => String::new(),
+                };
+                self.open_block(&format!("<{}><code{}>", html_tag(&Token::MaekCode), class));
+                self.push_output(&escape_text(&block.code));
+                self.close_block(&format!("</code></{}>", html_tag(&Token::MaekCode)));
+            }
+            Node::Heading(heading) => {
+                self.open_block(&format!("<h{}>", heading.level));
+                for child in &heading.content {
+                    self.analyze_inline(child);
+                }
+                self.close_block(&format!("</h{}>", heading.level));
+            }
+            //audio and iframes sit in a line of text like images do, so pretty mode leaves them where they are instead of giving them lines of their own (that would add visible spaces)
+            Node::Audio(media) => match self.check_url(&media.src, media.span) {
+                Ok(src) => {
+                    let audio = html_tag(&Token::GimmehSoundz);
+                    self.push_inline(&format!("<{} controls> <source src=\"{}\"></{}>", audio, escape_attribute(&src), audio));
+                }
+                Err(error) => self.errors.push(error),
+            },
+            Node::Video(media) => match self.check_url(&media.src, media.span) {
+                Ok(src) => {
+                    let video = html_tag(&Token::GimmehVidz);
+                    self.push_inline(&format!("<{} src=\"{}\"></{}>", video, escape_attribute(&src), video));
+                }
+                Err(error) => self.errors.push(error),
+            },
+            Node::Comment(comment) => {
+                //escaping a comment keeps a stray "-->" inside it from ending the comment early
+                self.open_block("<!--");
+                self.push_output(&escape_text(comment));
+                self.close_block("-->");
+            }
+            Node::Define(variable) => {
+                if self.paragraph_scope {
+                    self.paragraph_var.insert(variable.name.clone(), variable.value.clone());
+                } else {
+                    self.body_var.insert(variable.name.clone(), variable.value.clone());
+                }
+            }
+            Node::Inline(inline) => self.analyze_inline(inline),
+        }
+    }
+    fn analyze_inline(&mut self, inline: &Inline){
+        match inline {
+            Inline::Text(text) => self.push_inline(&escape_text(text)),
+            Inline::Styled(style, content) => {
+                let keyword = style_keyword(*style);
+                self.open_tag(&keyword);
+                for child in content {
+                    self.analyze_inline(child);
+                }
+                self.close_tag(&keyword);
+            }
+            Inline::Newline => self.open_tag(&Token::GimmehNewline),
+            Inline::Variable(variable) => match self.lookup_variable(variable) {
+                Ok(value) => self.push_inline(&escape_text(&value)),
+                Err(error) => self.errors.push(error),
+            },
+            Inline::Raw(markup) => self.push_inline(markup),
+            Inline::Image(image) => {
+                if let Some(attributes) = self.image_attributes(image) {
+                    self.push_inline(&format!("<{}{}>", html_tag(&Token::GimmehPikchur), attributes));
+                }
+            }
+            Inline::Link(link) => {
+                if self.in_link {
+                    self.errors.push(CompileError::new(ErrorKind::Semantic, "a link cant go inside another link", link.span));
+                    return;
+                }
+                match self.check_url(&link.href, link.span) {
+                    Ok(href) => {
+                        self.push_inline(&format!("<{} href=\"{}\">", html_tag(&Token::GimmehLink), escape_attribute(&href)));
+                        self.in_link = true;
+                        for child in &link.content {
+                            self.analyze_inline(child);
+                        }
+                        self.in_link = false;
+                        self.close_tag(&Token::GimmehLink);
+                    }
+                    Err(error) => self.errors.push(error),
+                }
+            }
+        }
+    }
+    //turns the settings of a numbered list into start and type attributes, a setting that doesnt make sense is an error and gets left off
+    fn list_attributes(&mut self, list: &List) -> String{
+        let mut attributes = String::new();
+        if let Some(start) = &list.start {
+            match start.value.parse::<i64>() {
+                Ok(number) => attributes.push_str(&format!(" start=\"{}\"", number)),
+                Err(_) => self.errors.push(CompileError::new(
+                    ErrorKind::Semantic,
+                    format!("a list has to start at a whole number, not '{}'", start.value),
+                    start.span,
+                )),
+            }
+        }
+        if let Some(style) = &list.style {
+            match list_style(&style.value) {
+                Some(kind) => attributes.push_str(&format!(" type=\"{}\"", kind)),
+                None => self.errors.push(CompileError::new(
+                    ErrorKind::Semantic,
+                    format!("unknown list style '{}', expected one of numbers, letters, LETTERS, roman or ROMAN", style.value),
+                    style.span,
+                )),
+            }
+        }
+        attributes
+    }
+    //rows at the top made of nothing but header cells go in a <thead>, everything else in the <tbody>
+    //every row is expected to have as many cells as the first one, any that dont still come out but get a warning
+    fn analyze_table(&mut self, table: &Table){
+        let columns = table.rows.first().map_or(0, |row| row.cells.len());
+        let cells = |count: usize| if count == 1 { "1 cell".to_string() } else { format!("{} cells", count) };
+        for row in &table.rows {
+            if row.cells.len() != columns {
+                self.warnings.push(CompileError::warning(
+                    ErrorKind::Semantic,
+                    format!("row has {} but the first row of the table has {}", cells(row.cells.len()), columns),
+                    row.span,
+                ));
+            }
+        }
+        let head_rows = table.rows.iter().take_while(|row| !row.cells.is_empty() && row.cells.iter().all(|cell| cell.header)).count();
+        let (head, body) = table.rows.split_at(head_rows);
+        self.open_block(&format!("<{}>", html_tag(&Token::MaekTable)));
+        for (section, rows) in [("thead", head), ("tbody", body)] {
+            if rows.is_empty() {
+                continue;
+            }
+            self.open_block(&format!("<{}>", section));
+            for row in rows {
+                self.open_block(&format!("<{}>", html_tag(&Token::MaekRow)));
+                for cell in &row.cells {
+                    let tag = html_tag(if cell.header { &Token::GimmehHeaderCell } else { &Token::GimmehCell });
+                    self.open_block(&format!("<{}>", tag));
+                    for child in &cell.content {
+                        self.analyze_node(child);
+                    }
+                    self.close_block(&format!("</{}>", tag));
+                }
+                self.close_block(&format!("</{}>", html_tag(&Token::MaekRow)));
+            }
+            self.close_block(&format!("</{}>", section));
+        }
+        self.close_block(&format!("</{}>", html_tag(&Token::MaekTable)));
+    }
+    //an image without alt text is useless to anyone using a screen reader, so its an error rather than something to quietly fill in
+    //a bad size is reported but the image still comes out, just without that attribute
+    fn image_attributes(&mut self, image: &Image) -> Option<String>{
+        let src = match self.check_url(&image.src.src, image.src.span) {
+            Ok(src) => src,
+            Err(error) => {
+                self.errors.push(error);
+                return None;
+            }
+        };
+        let alt = match &image.alt {
+            Some(alt) => alt,
+            None => {
+                self.errors.push(CompileError::new(
+                    ErrorKind::Semantic,
+                    "image is missing its alt text, describe it with #IT SEZ (or use an empty #IT SEZ #MKAY if its only decoration)",
+                    image.span,
+                ));
+                return None;
+            }
+        };
+        let mut attributes = format!(" src=\"{}\" alt=\"{}\"", escape_attribute(&src), escape_attribute(alt));
+        for (name, setting) in [("width", &image.width), ("height", &image.height)] {
+            if let Some(setting) = setting {
+                match pixels(setting) {
+                    Ok(size) => attributes.push_str(&format!(" {}=\"{}\"", name, size)),
+                    Err(error) => self.errors.push(error),
+                }
+            }
+        }
+        Some(attributes)
+    }
+    //the whitespace around a url is just formatting so its trimmed off, anything wrong with whats left is an error pointing at the url
+    fn check_url(&self, url: &str, span: Span) -> Result<String, CompileError>{
+        let url = url.trim();
+        match self.url_policy.check(url) {
+            Ok(()) => Ok(url.to_string()),
+            Err(message) => Err(CompileError::new(ErrorKind::Semantic, message, span)),
+        }
+    }
+    //when a variable is called, if its in the body, it checks the body hashmap for the variable, if its there, cool, returns its value, if not, returns an error
+    //if a variable is called in a paragraph, it checks the paragraph hashmap first, if its there, cool, returns the value, if not, it checks the body one, if its there, cool, returns the value, if not, returns an error
+    fn lookup_variable(&self, variable: &VariableUse) -> Result<String, CompileError>{
+        let in_paragraph = if self.paragraph_scope { self.paragraph_var.get(&variable.name) } else { None };
+        match in_paragraph.or_else(|| self.body_var.get(&variable.name)) {
+            Some(value) => Ok(value.clone()),
+            None => Err(CompileError::new(
+                ErrorKind::Semantic,
+                format!("variable {} not found in scope", variable.name),
+                variable.span,
+            )),
+        }
+    }
+}
+
+//nodes that can only go where html5 expects flow content, so they cant be inside a <p>
+fn is_flow_only(node: &Node) -> bool {
+    matches!(node, Node::List(_) | Node::Table(_) | Node::Code(_) | Node::Section(_) | Node::Quote(_) | Node::Rule)
+}
+
+//the keyword each kind of section is written with, which is where its element name comes from
+fn section_keyword(kind: SectionKind) -> Token {
+    match kind {
+        SectionKind::Section => Token::MaekSection,
+        SectionKind::Article => Token::MaekArticle,
+        SectionKind::Header => Token::MaekHeader,
+        SectionKind::Footer => Token::MaekFooter,
+        SectionKind::Nav => Token::MaekNav,
+        SectionKind::Div => Token::MaekDiv,
+    }
+}
+
+//the name attribute of each kind of meta tag
+fn meta_name(name: MetaName) -> &'static str {
+    match name {
+        MetaName::Description => "description",
+        MetaName::Author => "author",
+        MetaName::Keywords => "keywords",
+        MetaName::Viewport => "viewport",
+    }
+}
+
+//same idea as section_keyword, for the text styles
+fn style_keyword(style: Style) -> Token {
+    match style {
+        Style::Bold => Token::GimmehBold,
+        Style::Italics => Token::GimmehItalics,
+        Style::Underline => Token::GimmehUnderline,
+        Style::Strikethrough => Token::GimmehStrikethru,
+        Style::Code => Token::GimmehCode,
+        Style::Subscript => Token::GimmehSubscript,
+        Style::Superscript => Token::GimmehSuperscript,
+        Style::Highlight => Token::GimmehHighlight,
+        Style::Small => Token::GimmehSmol,
+    }
+}
+
+//image sizes are whole numbers of pixels, and an image thats 0 pixels wide might as well not be there
+fn pixels(setting: &Setting) -> Result<u32, CompileError> {
+    match setting.value.parse::<u32>() {
+        Ok(size) if size > 0 => Ok(size),
+        _ => Err(CompileError::new(
+            ErrorKind::Semantic,
+            format!("image size has to be a whole number of pixels, not '{}'", setting.value),
+            setting.span,
+        )),
+    }
+}
+
+//the value of the type attribute for each list style, the html values themselves work too
+fn list_style(style: &str) -> Option<&'static str> {
+    match style {
+        "numbers" | "1" => Some("1"),
+        "letters" | "a" => Some("a"),
+        "LETTERS" | "A" => Some("A"),
+        "roman" | "i" => Some("i"),
+        "ROMAN" | "I" => Some("I"),
+        _ => None,
+    }
+}
+
+//escaping for anything that ends up between tags
+fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+//escaping for anything that ends up inside a quoted attribute value, quotes need it here too
+fn escape_attribute(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,202 @@
+//! Tokens produced by the lexer and the spans that say where each one came from.
+
+use std::fmt;
+
+use crate::keywords::{self, KeywordKind};
+
+/// Where a token starts in the source file and how far it runs.
+/// `offset` and `len` are in bytes, `line` and `column` are both 1-based (column counts characters, not bytes).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    /// Bytes from the start of the file.
+    pub offset: usize,
+    /// Length in bytes.
+    pub len: usize,
+    /// Line number, starting at 1.
+    pub line: usize,
+    /// Column in characters, starting at 1.
+    pub column: usize,
+}
+
+impl Default for Span {
+    fn default() -> Self {
+        Self { offset: 0, len: 0, line: 1, column: 1 }
+    }
+}
+
+/// Every token the lexer can produce, one variant per keyword plus `Text` for everything in between.
+/// `Verbatim` is the inside of a code or style block, which the lexer hands over exactly as written.
+/// `Eof` is pushed once at the end of the token stream so the parser always has something to look at.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Token {
+    /// `#HAI`
+    Hai,
+    /// `#KTHXBYE`
+    Kthxbye,
+    /// `#OBTW`
+    Obtw,
+    /// `#TLDR`
+    Tldr,
+    /// `#MAEK HEAD`
+    MaekHead,
+    /// `#OIC`
+    Oic,
+    /// `#GIMMEH TITLE`
+    GimmehTitle,
+    /// `#MKAY`
+    Mkay,
+    /// `#MAEK PARAGRAF`
+    MaekParagraf,
+    /// `#GIMMEH BOLD`
+    GimmehBold,
+    /// `#GIMMEH ITALICS`
+    GimmehItalics,
+    /// `#MAEK LIST`
+    MaekList,
+    /// `#GIMMEH ITEM`
+    GimmehItem,
+    /// `#GIMMEH NEWLINE`
+    GimmehNewline,
+    /// `#GIMMEH SOUNDZ`
+    GimmehSoundz,
+    /// `#GIMMEH VIDZ`
+    GimmehVidz,
+    /// `#I HAZ`
+    IHaz,
+    /// `#IT IZ`
+    ItIz,
+    /// `#LEMME SEE`
+    LemmeSee,
+    /// `#GIMMEH HTML`
+    GimmehHtml,
+    /// `#MAEK NUMBERZ`
+    MaekNumberz,
+    /// `#STARTIN AT`
+    StartinAt,
+    /// `#LOOKIN LIEK`
+    LookinLiek,
+    /// `#GIMMEH HEADR`
+    GimmehHeadr,
+    /// `#GIMMEH LINK`
+    GimmehLink,
+    /// `#IT SEZ`
+    ItSez,
+    /// `#GIMMEH PIKCHUR`
+    GimmehPikchur,
+    /// `#WIDF`
+    Widf,
+    /// `#HITE`
+    Hite,
+    /// `#MAEK TABLE`
+    MaekTable,
+    /// `#MAEK ROW`
+    MaekRow,
+    /// `#GIMMEH HEADER CELL`
+    GimmehHeaderCell,
+    /// `#GIMMEH CELL`
+    GimmehCell,
+    /// `#MAEK CODE`
+    MaekCode,
+    /// `#KTHX CODE`
+    KthxCode,
+    /// `#MAEK SECTION`
+    MaekSection,
+    /// `#MAEK ARTICLE`
+    MaekArticle,
+    /// `#MAEK HEADER`
+    MaekHeader,
+    /// `#MAEK FOOTER`
+    MaekFooter,
+    /// `#MAEK NAV`
+    MaekNav,
+    /// `#MAEK DIV`
+    MaekDiv,
+    /// `#MAEK QUOTE`
+    MaekQuote,
+    /// `#FROM`
+    From,
+    /// `#GIMMEH RULE`
+    GimmehRule,
+    /// `#GIMMEH UNDERLINE`
+    GimmehUnderline,
+    /// `#GIMMEH STRIKETHRU`
+    GimmehStrikethru,
+    /// `#GIMMEH CODE`
+    GimmehCode,
+    /// `#GIMMEH SUBSCRIPT`
+    GimmehSubscript,
+    /// `#GIMMEH SUPERSCRIPT`
+    GimmehSuperscript,
+    /// `#GIMMEH HIGHLIGHT`
+    GimmehHighlight,
+    /// `#GIMMEH SMOL`
+    GimmehSmol,
+    /// `#GIMMEH DESCRIPSHUN`
+    GimmehDescripshun,
+    /// `#GIMMEH AUTHOR`
+    GimmehAuthor,
+    /// `#GIMMEH KEYWORDZ`
+    GimmehKeywordz,
+    /// `#GIMMEH VIEWPORT`
+    GimmehViewport,
+    /// `#GIMMEH STYLESHEET`
+    GimmehStylesheet,
+    /// `#MAEK STYLE`
+    MaekStyle,
+    /// `#KTHX STYLE`
+    KthxStyle,
+    /// `#GIMMEH SCRIPT`
+    GimmehScript,
+    /// `#GIMMEH ICON`
+    GimmehIcon,
+    /// Everything between tags.
+    Text(String),
+    /// The inside of a code or style block.
+    Verbatim(String),
+    /// The end of the token stream.
+    Eof,
+}
+
+impl Token {
+    /// The spelling of the token as it appears in a .lol file (keywords are always shown uppercase).
+    pub fn lexeme(&self) -> &str {
+        match self {
+            Token::Text(text) | Token::Verbatim(text) => text,
+            Token::Eof => "end of file",
+            keyword => match keywords::by_token(keyword) {
+                Some(entry) => entry.spelling,
+                None => unreachable!("{:?} is missing from the keyword registry", keyword),
+            },
+        }
+    }
+
+    /// Whether this is a `Text` token.
+    pub fn is_text(&self) -> bool {
+        matches!(self, Token::Text(_))
+    }
+
+    /// Keywords that open a block level construct, the parser uses these as places to pick back up after an error.
+    pub fn starts_block(&self) -> bool {
+        keywords::by_token(self).is_some_and(|entry| entry.block)
+    }
+
+    /// Keywords that finish a construct (`#OIC`, `#MKAY` and friends).
+    pub fn is_closer(&self) -> bool {
+        keywords::by_token(self).is_some_and(|entry| entry.kind == KeywordKind::Closer)
+    }
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.lexeme())
+    }
+}
+
+/// A token together with the place it was found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpannedToken {
+    /// The token itself.
+    pub token: Token,
+    /// Where it was found.
+    pub span: Span,
+}
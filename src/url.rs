@@ -0,0 +1,181 @@
+//! Checking every url in the source before it ends up in an attribute: audio and video sources, links, images,
+//! quote cites, and the stylesheets, scripts and icon in the head.
+
+/// Which urls the page is allowed to point at, for anything in the source that takes a url.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UrlPolicy {
+    /// Schemes that may be used, lowercase and without the ':' (`http`, `https`, ...).
+    pub schemes: Vec<String>,
+    /// Whether urls without a scheme (`clips/intro.mp3`, `/media/theme.mp3`) are allowed.
+    /// This only covers paths, a url starting with `//` points at another host and always needs a scheme.
+    pub allow_relative: bool,
+}
+
+impl UrlPolicy {
+    /// Allows http, https and relative urls.
+    pub fn new() -> Self {
+        Self { schemes: vec!["http".to_string(), "https".to_string()], allow_relative: true }
+    }
+    /// Checks that `url` is well formed and allowed, saying what was wrong with it if not.
+    //makes sure the url is well formed and allowed, the message says what was wrong with it if not
+    //the url is expected to be trimmed already, whitespace anywhere inside it is an error
+    pub fn check(&self, url: &str) -> Result<(), String> {
+        if url.is_empty() {
+            return Err("expected a url but the source is empty".to_string());
+        }
+        if url.chars().any(char::is_whitespace) {
+            return Err(format!("url '{}' contains whitespace", url));
+        }
+        if let Some(c) = url.chars().find(|c| c.is_control() || matches!(c, '"' | '<' | '>' | '\\' | '^' | '`' | '{' | '|' | '}')) {
+            let shown = if c.is_control() { c.escape_default().to_string() } else { c.to_string() };
+            return Err(format!("url '{}' contains the character '{}', which has to be percent encoded", url, shown));
+        }
+        if let Some(position) = bad_percent_escape(url) {
+            return Err(format!("url '{}' has a broken percent escape at '{}'", url, &url[position..]));
+        }
+        let rest = match scheme(url) {
+            Some(scheme) => {
+                if !self.schemes.iter().any(|allowed| allowed.eq_ignore_ascii_case(scheme)) {
+                    return Err(format!("url scheme '{}' is not allowed here", scheme.to_lowercase()));
+                }
+                let rest = &url[scheme.len() + 1..];
+                //web urls always have a host, "http:foo" or "https://" are just typos
+                if matches!(scheme.to_lowercase().as_str(), "http" | "https") && !rest.starts_with("//") {
+                    return Err(format!("url '{}' is missing the '//' and host after its scheme", url));
+                }
+                rest
+            }
+            //"//evil.example/x" would load from whatever host it names, over whatever scheme the page happens to use, so it isnt treated as a path
+            None if url.starts_with("//") => return Err(format!("url '{}' needs a scheme, without one it could load from any host", url)),
+            None if self.allow_relative => url,
+            None => return Err(format!("relative url '{}' is not allowed here, it needs a scheme", url)),
+        };
+        if let Some(authority) = rest.strip_prefix("//") {
+            let host = authority.split(['/', '?', '#']).next().unwrap_or("");
+            let host = host.rsplit('@').next().unwrap_or("");
+            if host.is_empty() || host.starts_with(':') {
+                return Err(format!("url '{}' is missing a host", url));
+            }
+            //the port is optional, but when its there it has to be a number (ipv6 hosts are in brackets since they have ':'s of their own)
+            let after_host = match host.strip_prefix('[') {
+                Some(ipv6) => ipv6.split_once(']').map_or("", |(_, after)| after),
+                None => host.find(':').map_or("", |colon| &host[colon..]),
+            };
+            if let Some(port) = after_host.strip_prefix(':') && !port.chars().all(|c| c.is_ascii_digit()) {
+                return Err(format!("url '{}' has a port that isnt a number", url));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Default for UrlPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+//the scheme is everything before the first ':', as long as it looks like a scheme and comes before any '/', '?' or '#'
+//"clips/a:b.mp3" doesnt have one, "javascript:alert(1)" does
+fn scheme(url: &str) -> Option<&str> {
+    let end = url.find([':', '/', '?', '#'])?;
+    if !url[end..].starts_with(':') {
+        return None;
+    }
+    let scheme = &url[..end];
+    let mut chars = scheme.chars();
+    let starts_with_letter = chars.next().is_some_and(|c| c.is_ascii_alphabetic());
+    if starts_with_letter && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.')) {
+        Some(scheme)
+    } else {
+        None
+    }
+}
+
+//finds the first '%' that isnt followed by two hex digits
+fn bad_percent_escape(url: &str) -> Option<usize> {
+    let bytes = url.as_bytes();
+    bytes.iter().enumerate().position(|(i, &b)| {
+        b == b'%' && !(bytes.get(i + 1).is_some_and(u8::is_ascii_hexdigit) && bytes.get(i + 2).is_some_and(u8::is_ascii_hexdigit))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::UrlPolicy;
+
+    fn rejected(url: &str) -> String {
+        UrlPolicy::default().check(url).expect_err(url)
+    }
+
+    #[test]
+    fn web_and_relative_urls_are_allowed() {
+        let policy = UrlPolicy::default();
+        for url in [
+            "https://example.com",
+            "http://example.com/a/b?c=d#e",
+            "HTTPS://EXAMPLE.COM",
+            "https://user@example.com:8080/",
+            "https://[::1]:8080/a.mp3",
+            "https://example.com:/a.mp3",
+            "clips/intro.mp3",
+            "/media/theme.mp3",
+            "../up.png",
+            "?page=2",
+            "#top",
+            "clips/a:b.mp3",
+            "caf%C3%A9.png",
+        ] {
+            assert_eq!(policy.check(url), Ok(()), "{}", url);
+        }
+    }
+
+    #[test]
+    fn other_schemes_are_rejected() {
+        assert_eq!(rejected("javascript:alert(1)"), "url scheme 'javascript' is not allowed here");
+        assert_eq!(rejected("JAVASCRIPT:alert(1)"), "url scheme 'javascript' is not allowed here");
+        assert_eq!(rejected("JavaScript:alert(1)"), "url scheme 'javascript' is not allowed here");
+        assert_eq!(rejected("data:text/html,hi"), "url scheme 'data' is not allowed here");
+        assert_eq!(rejected("vbscript:x"), "url scheme 'vbscript' is not allowed here");
+        assert_eq!(rejected("//evil.example/x"), "url '//evil.example/x' needs a scheme, without one it could load from any host");
+        let https_only = UrlPolicy { schemes: vec!["https".to_string()], allow_relative: true };
+        assert_eq!(https_only.check("//evil.example/x"), Err("url '//evil.example/x' needs a scheme, without one it could load from any host".to_string()));
+    }
+
+    #[test]
+    fn characters_that_would_break_the_attribute_are_rejected() {
+        assert_eq!(rejected("a\"onerror=\"x"), "url 'a\"onerror=\"x' contains the character '\"', which has to be percent encoded");
+        assert_eq!(rejected("a<b"), "url 'a<b' contains the character '<', which has to be percent encoded");
+        assert_eq!(rejected("a\u{7}b"), "url 'a\u{7}b' contains the character '\\u{7}', which has to be percent encoded");
+        assert_eq!(rejected("a b.png"), "url 'a b.png' contains whitespace");
+        assert_eq!(rejected("a\tb.png"), "url 'a\tb.png' contains whitespace");
+        assert_eq!(rejected(""), "expected a url but the source is empty");
+    }
+
+    #[test]
+    fn broken_percent_escapes_are_rejected() {
+        assert_eq!(rejected("100%.png"), "url '100%.png' has a broken percent escape at '%.png'");
+        assert_eq!(rejected("a%2"), "url 'a%2' has a broken percent escape at '%2'");
+        assert_eq!(rejected("a%zz"), "url 'a%zz' has a broken percent escape at '%zz'");
+    }
+
+    #[test]
+    fn web_urls_need_a_host() {
+        assert_eq!(rejected("http:foo"), "url 'http:foo' is missing the '//' and host after its scheme");
+        assert_eq!(rejected("https://"), "url 'https://' is missing a host");
+        assert_eq!(rejected("https://:80/x"), "url 'https://:80/x' is missing a host");
+        assert_eq!(rejected("https:///x"), "url 'https:///x' is missing a host");
+        assert_eq!(rejected("https://host:notaport/a.mp3"), "url 'https://host:notaport/a.mp3' has a port that isnt a number");
+        assert_eq!(rejected("https://[::1]:8o/a.mp3"), "url 'https://[::1]:8o/a.mp3' has a port that isnt a number");
+    }
+
+    #[test]
+    fn custom_policies() {
+        let https_only = UrlPolicy { schemes: vec!["https".to_string()], allow_relative: false };
+        assert_eq!(https_only.check("https://example.com"), Ok(()));
+        assert_eq!(https_only.check("http://example.com"), Err("url scheme 'http' is not allowed here".to_string()));
+        assert_eq!(https_only.check("clips/a.mp3"), Err("relative url 'clips/a.mp3' is not allowed here, it needs a scheme".to_string()));
+        let with_mail = UrlPolicy { schemes: vec!["mailto".to_string()], allow_relative: true };
+        assert_eq!(with_mail.check("MAILTO:cat@example.com"), Ok(()));
+    }
+}
@@ -0,0 +1,218 @@
+//! Checks the errors the compiler reports, what they say, where they point and the fixes they suggest.
+
+use lolcode_compiler::keywords::similar;
+use lolcode_compiler::{compile_str, Applicability, Diagnostics, ErrorKind, Options, UrlPolicy};
+
+fn errors(source: &str) -> Diagnostics {
+    compile_str(source, &Options::default()).expect_err("document should not compile")
+}
+
+#[test]
+fn similar_finds_the_closest_keywords() {
+    let spellings = |tag: &str| similar(tag).iter().map(|keyword| keyword.spelling).collect::<Vec<_>>();
+    assert_eq!(spellings("#MAKE PARAGRAF"), ["#MAEK PARAGRAF"]);
+    assert_eq!(spellings("#MKYA"), ["#MKAY"]);
+    assert_eq!(spellings("#GIMMEH BOLDD"), ["#GIMMEH BOLD"]);
+    assert!(spellings("#WHATEVER").is_empty());
+    //one edit away from both, so neither is picked over the other
+    assert_eq!(spellings("#MAEK NIV"), ["#MAEK NAV", "#MAEK DIV"]);
+}
+
+#[test]
+fn typo_is_rendered_with_a_suggestion() {
+    let diagnostics = errors("#HAI\n#MAKE PARAGRAF hi #OIC\n#KTHXBYE\n");
+    assert_eq!(
+        diagnostics.to_string(),
+        "lexical error: unknown tag #MAKE PARAGRAF; did you mean #MAEK PARAGRAF?\n --> <input>:2:1\n  |\n2 | #MAKE PARAGRAF hi #OIC\n  | ^^^^^^^^^^^^^^\n\nerror: aborting due to 1 previous error\n"
+    );
+}
+
+#[test]
+fn unknown_tag_without_anything_close() {
+    let diagnostics = errors("#HAI #WHATEVER #KTHXBYE");
+    assert_eq!(diagnostics.items.len(), 1);
+    assert_eq!(diagnostics.items[0].message, "unknown tag #WHATEVER");
+    assert!(diagnostics.items[0].suggestions.is_empty());
+}
+
+#[test]
+fn apply_suggestions_fixes_typos() {
+    let diagnostics = errors("#HAI\n#MAKE PARAGRAF hi #MKYA #OIC\n#KTHXBYE\n");
+    assert_eq!(diagnostics.apply_suggestions(), "#HAI\n#MAEK PARAGRAF hi #MKAY #OIC\n#KTHXBYE\n");
+}
+
+#[test]
+fn apply_suggestions_keeps_punctuation_after_a_keyword() {
+    let source = "#HAI\n#I HAZ x #IT IZ Jon #MKAY\nHi #LEMME SEE x #MKAY, there. wow#mkay! (#OIC)\n#KTHXBYE\n";
+    let diagnostics = errors(source);
+    let messages: Vec<&str> = diagnostics.items.iter().map(|error| error.message.as_str()).collect();
+    assert_eq!(
+        messages,
+        [
+            "unknown tag #MKAY,; did you mean #MKAY ,? (a keyword has to be followed by whitespace)",
+            "unknown tag #MKAY!; did you mean #MKAY !? (a keyword has to be followed by whitespace)",
+            "unknown tag #OIC); did you mean #OIC )? (a keyword has to be followed by whitespace)",
+        ]
+    );
+    assert!(diagnostics.items.iter().all(|error| error.suggestions[0].applicability == Applicability::MachineApplicable));
+    assert_eq!(
+        diagnostics.apply_suggestions(),
+        "#HAI\n#I HAZ x #IT IZ Jon #MKAY\nHi #LEMME SEE x #MKAY , there. wow#mkay ! (#OIC )\n#KTHXBYE\n"
+    );
+}
+
+#[test]
+fn ambiguous_suggestions_are_not_applied() {
+    let diagnostics = errors("#HAI #MAEK NIV hi #OIC #KTHXBYE");
+    let suggestions = &diagnostics.items[0].suggestions;
+    assert_eq!(suggestions.len(), 1);
+    assert_eq!(suggestions[0].applicability, Applicability::MaybeIncorrect);
+    assert_eq!(diagnostics.items[0].message, "unknown tag #MAEK NIV; did you mean #MAEK NAV?");
+    assert_eq!(diagnostics.apply_suggestions(), "#HAI #MAEK NIV hi #OIC #KTHXBYE");
+}
+
+#[test]
+fn bad_url_error_points_at_the_url() {
+    let diagnostics = errors("#HAI\n#GIMMEH VIDZ   javascript:alert(1) #MKAY\n#KTHXBYE\n");
+    assert_eq!(
+        diagnostics.to_string(),
+        "static semantic error: url scheme 'javascript' is not allowed here\n --> <input>:2:16\n  |\n2 | #GIMMEH VIDZ   javascript:alert(1) #MKAY\n  |                ^^^^^^^^^^^^^^^^^^^\n\nerror: aborting due to 1 previous error\n"
+    );
+}
+
+#[test]
+fn url_policy_comes_from_the_options() {
+    let source = "#HAI #GIMMEH LINK mailto:cat@example.com #IT SEZ mail #MKAY #GIMMEH SOUNDZ clips/a.mp3 #MKAY #KTHXBYE";
+    assert!(compile_str(source, &Options::default()).is_err());
+    let options = Options { urls: UrlPolicy { schemes: vec!["mailto".to_string()], allow_relative: false }, ..Options::default() };
+    let diagnostics = compile_str(source, &options).expect_err("relative urls are turned off");
+    let messages: Vec<&str> = diagnostics.items.iter().map(|error| error.message.as_str()).collect();
+    assert_eq!(messages, ["relative url 'clips/a.mp3' is not allowed here, it needs a scheme"]);
+    let options = Options { urls: UrlPolicy { schemes: vec!["mailto".to_string()], allow_relative: true }, ..Options::default() };
+    let html = compile_str(source, &options).expect("both urls are allowed").html;
+    assert!(html.contains("<a href=\"mailto:cat@example.com\">mail </a>"), "{}", html);
+}
+
+#[test]
+fn heading_levels_go_from_1_to_6() {
+    for level in ["0", "7", "+2", "02", "two", "1.5"] {
+        let diagnostics = errors(&format!("#HAI #GIMMEH HEADR {} z #MKAY #KTHXBYE", level));
+        assert_eq!(diagnostics.items.len(), 1, "{}", diagnostics);
+        assert_eq!(diagnostics.items[0].kind, ErrorKind::Syntax);
+        assert_eq!(diagnostics.items[0].message, format!("heading level has to be a number from 1 to 6, not '{}'", level));
+        assert_eq!((diagnostics.items[0].location.column, diagnostics.items[0].location.len), (20, level.len()));
+    }
+    for level in 1..=6 {
+        let html = compile_str(&format!("#HAI #GIMMEH HEADR {} z #MKAY #KTHXBYE", level), &Options::default()).expect("valid level").html;
+        assert!(html.contains(&format!("<h{0}>z </h{0}>", level)), "{}", html);
+    }
+}
+
+#[test]
+fn syntax_and_scope_errors_are_reported_together_in_source_order() {
+    let source = "#HAI
+#MAEK PARAGRAF #GIMMEH BOLD #MKAY #OIC
+#LEMME SEE nobody #MKAY
+#I HAZ two words #IT IZ x #MKAY
+#MAEK PARAGRAF #LEMME SEE inner #MKAY #OIC
+#KTHXBYE
+";
+    let diagnostics = errors(source);
+    let found: Vec<(ErrorKind, usize, &str)> =
+        diagnostics.items.iter().map(|error| (error.kind, error.location.line, error.message.as_str())).collect();
+    assert_eq!(
+        found,
+        [
+            (ErrorKind::Syntax, 2, "expected text but found #MKAY instead"),
+            (ErrorKind::Semantic, 3, "variable nobody not found in scope"),
+            (ErrorKind::Syntax, 4, "two words is an invalid variable name"),
+            (ErrorKind::Semantic, 5, "variable inner not found in scope"),
+        ]
+    );
+    let rendered = diagnostics.to_string();
+    assert!(rendered.starts_with("syntax error: expected text but found #MKAY instead\n --> <input>:2:29\n"), "{}", rendered);
+    assert!(rendered.ends_with("\nerror: aborting due to 4 previous errors\n"), "{}", rendered);
+}
+
+//lists inside list items, `depth` of them, each item holding an x and the next list
+fn nested_lists(depth: usize) -> String {
+    format!("#HAI\n{}{}\n#KTHXBYE\n", "#MAEK LIST #GIMMEH ITEM x ".repeat(depth), "#MKAY #OIC ".repeat(depth))
+}
+
+#[test]
+fn lists_nested_too_deep_are_an_error_not_a_crash() {
+    let html = compile_str(&nested_lists(256), &Options::default()).expect("256 levels is allowed").html;
+    assert_eq!(html.matches("<ul>").count(), 256);
+
+    let diagnostics = errors(&nested_lists(10_000));
+    assert_eq!(diagnostics.items.len(), 1, "{}", diagnostics);
+    assert_eq!(diagnostics.items[0].kind, ErrorKind::Syntax);
+    assert_eq!(diagnostics.items[0].message, "#MAEK LIST is nested too deep, things can only go 256 levels inside each other");
+    //the 257th list, everything inside it is skipped so theres only the one error
+    assert_eq!(diagnostics.items[0].location.column, 1 + 256 * "#MAEK LIST #GIMMEH ITEM x ".len());
+}
+
+#[test]
+fn styles_sections_and_quotes_nested_too_deep_are_an_error_not_a_crash() {
+    let bold = format!("#HAI #MAEK PARAGRAF {}{}#OIC #KTHXBYE", "#GIMMEH BOLD x ".repeat(10_000), "#MKAY ".repeat(10_000));
+    let divs = format!("#HAI {}{}#KTHXBYE", "#MAEK DIV x ".repeat(10_000), "#OIC ".repeat(10_000));
+    let quotes = format!("#HAI {}{}#KTHXBYE", "#MAEK QUOTE x ".repeat(10_000), "#OIC ".repeat(10_000));
+    for (source, keyword) in [(bold, "#GIMMEH BOLD"), (divs, "#MAEK DIV"), (quotes, "#MAEK QUOTE")] {
+        let diagnostics = errors(&source);
+        assert_eq!(diagnostics.items.len(), 1, "{}", diagnostics);
+        assert_eq!(diagnostics.items[0].message, format!("{} is nested too deep, things can only go 256 levels inside each other", keyword));
+    }
+
+    //everything that nests counts towards the same limit
+    let mixed = |italics: usize| {
+        format!(
+            "#HAI {}#MAEK PARAGRAF {}x{} #OIC {}#KTHXBYE",
+            "#MAEK SECTION ".repeat(200),
+            "#GIMMEH ITALICS ".repeat(italics),
+            "#MKAY ".repeat(italics),
+            "#OIC ".repeat(200)
+        )
+    };
+    assert!(compile_str(&mixed(56), &Options::default()).is_ok());
+    let diagnostics = errors(&mixed(57));
+    assert_eq!(diagnostics.items.len(), 1, "{}", diagnostics);
+    assert_eq!(diagnostics.items[0].message, "#GIMMEH ITALICS is nested too deep, things can only go 256 levels inside each other");
+}
+
+#[test]
+fn code_block_errors() {
+    let diagnostics = errors("#HAI\n#MAEK CODE rust\nfn main() {}\n#KTHXBYE\n");
+    assert_eq!(diagnostics.items.len(), 1, "{}", diagnostics);
+    assert_eq!(diagnostics.items[0].kind, ErrorKind::Lexical);
+    assert_eq!(diagnostics.items[0].message, "code block is never closed, expected #KTHX CODE before the end of the file");
+    assert_eq!((diagnostics.items[0].location.line, diagnostics.items[0].location.column), (2, 1));
+
+    let diagnostics = errors("#HAI\n#MAEK CODE rust nightly\nfn main() {}\n#KTHX CODE\n#KTHXBYE\n");
+    assert_eq!(diagnostics.items.len(), 1, "{}", diagnostics);
+    assert_eq!(diagnostics.items[0].kind, ErrorKind::Syntax);
+    assert_eq!(diagnostics.items[0].message, "code block language has to be one word, not 'rust nightly'");
+    assert_eq!((diagnostics.items[0].location.line, diagnostics.items[0].location.column), (2, 12));
+}
+
+#[test]
+fn headers_and_footers_cant_hold_each_other() {
+    let diagnostics = errors("#HAI\n#MAEK HEADER #MAEK DIV #MAEK FOOTER x #OIC #OIC #OIC\n#MAEK FOOTER #MAEK FOOTER y #OIC #OIC\n#KTHXBYE");
+    let found: Vec<(usize, usize, &str)> =
+        diagnostics.items.iter().map(|error| (error.location.line, error.location.column, error.message.as_str())).collect();
+    assert_eq!(
+        found,
+        [(2, 24, "#MAEK FOOTER cant go inside a #MAEK HEADER"), (3, 14, "#MAEK FOOTER cant go inside a #MAEK FOOTER")]
+    );
+    assert!(diagnostics.items.iter().all(|error| error.kind == ErrorKind::Syntax));
+    //next to each other, or inside anything else, is fine
+    assert!(compile_str("#HAI #MAEK HEADER a #OIC #MAEK FOOTER b #OIC #MAEK ARTICLE #MAEK HEADER c #OIC #OIC #KTHXBYE", &Options::default()).is_ok());
+}
+
+#[test]
+fn section_missing_its_oic() {
+    let diagnostics = errors("#HAI\n#MAEK SECTION a #MAEK PARAGRAF b #OIC\n#KTHXBYE");
+    assert_eq!(diagnostics.items.len(), 1, "{}", diagnostics);
+    assert_eq!(diagnostics.items[0].kind, ErrorKind::Syntax);
+    assert_eq!(diagnostics.items[0].message, "expected #OIC but found #KTHXBYE instead");
+    assert_eq!((diagnostics.items[0].location.line, diagnostics.items[0].location.column), (3, 1));
+}
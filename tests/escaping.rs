@@ -0,0 +1,82 @@
+//! Checks that text from the source comes out of the compiler as text, never as markup, and that `#GIMMEH HTML` is the one way around that.
+
+use lolcode_compiler::{compile_str, Options};
+
+fn compile(source: &str) -> String {
+    compile_str(source, &Options::default()).expect("document should compile").html
+}
+
+//just whats between <body> and </body>
+fn body(source: &str) -> String {
+    let html = compile(source);
+    let start = html.find("<body>").expect("no <body>") + "<body>".len();
+    let end = html.rfind("</body>").expect("no </body>");
+    html[start..end].to_string()
+}
+
+#[test]
+fn text_is_escaped() {
+    assert_eq!(body("#HAI a < b & c #KTHXBYE"), "a &lt; b &amp; c ");
+    assert_eq!(body("#HAI <script>alert(1)</script> #KTHXBYE"), "&lt;script&gt;alert(1)&lt;/script&gt; ");
+    assert_eq!(body("#HAI #GIMMEH BOLD 1 > 0 #MKAY #KTHXBYE"), "<b>1 &gt; 0 </b>");
+}
+
+#[test]
+fn variable_values_are_escaped() {
+    let source = "#HAI #I HAZ x #IT IZ <b>&amp;</b> #MKAY #MAEK PARAGRAF #LEMME SEE x #MKAY #OIC #KTHXBYE";
+    assert_eq!(body(source), "<p>&lt;b&gt;&amp;amp;&lt;/b&gt; </p>");
+}
+
+#[test]
+fn head_text_is_escaped() {
+    let html = compile("#HAI #MAEK HEAD #GIMMEH TITLE Tom & Jerry <3 #MKAY #GIMMEH DESCRIPSHUN say \"hi\" & <bye> #MKAY #OIC #KTHXBYE");
+    assert!(html.contains("<title>Tom &amp; Jerry &lt;3 </title>"), "{}", html);
+    assert!(html.contains("<meta name=\"description\" content=\"say &quot;hi&quot; &amp; &lt;bye&gt;\">"), "{}", html);
+}
+
+#[test]
+fn comments_cant_end_early() {
+    let html = compile("#HAI #OBTW sneaky --> <script> #TLDR #KTHXBYE");
+    assert!(html.contains("<!--sneaky --&gt; &lt;script&gt; -->"), "{}", html);
+    assert_eq!(html.matches("-->").count(), 1, "{}", html);
+}
+
+#[test]
+fn attributes_are_escaped() {
+    let source = "#HAI #GIMMEH LINK https://x.com/?a=1&b='2' #IT SEZ x #MKAY #GIMMEH PIKCHUR a.png #IT SEZ \"big\" & 'bold' <cat> #MKAY #KTHXBYE";
+    assert_eq!(
+        body(source),
+        "<a href=\"https://x.com/?a=1&amp;b=&#39;2&#39;\">x </a><img src=\"a.png\" alt=\"&quot;big&quot; &amp; &#39;bold&#39; &lt;cat&gt;\">"
+    );
+}
+
+#[test]
+fn raw_html_is_passed_through() {
+    assert_eq!(body("#HAI #GIMMEH HTML <em class=\"x\">raw & real</em> #MKAY #KTHXBYE"), "<em class=\"x\">raw & real</em> ");
+    assert_eq!(body("#HAI a & #GIMMEH HTML &amp; #MKAY #KTHXBYE"), "a &amp; &amp; ");
+}
+
+#[test]
+fn escaped_hash_is_just_text() {
+    assert_eq!(body(r"#HAI issue \#42 #KTHXBYE"), "issue #42 ");
+    assert_eq!(body(r"#HAI \#HAI is how it starts #KTHXBYE"), "#HAI is how it starts ");
+    assert_eq!(body(r"#HAI #GIMMEH BOLD \#1 #MKAY #KTHXBYE"), "<b>#1 </b>");
+}
+
+#[test]
+fn other_backslashes_are_kept() {
+    assert_eq!(body(r"#HAI C:\lol\cats \n \\ #KTHXBYE"), r"C:\lol\cats \n \\ ");
+    //the first backslash is kept, the second one escapes the #
+    assert_eq!(body(r"#HAI a\\#b #KTHXBYE"), r"a\#b ");
+}
+
+#[test]
+fn code_blocks_keep_every_character() {
+    let source = "#HAI\n#MAEK CODE rust\nfn main() {\n\tlet x = 1 < 2;   \n\n    // #MAEK PARAGRAF \\# & #KTHX\n}\n#KTHX CODE\n#KTHXBYE\n";
+    assert_eq!(
+        body(source),
+        "<pre><code class=\"language-rust\">fn main() {\n\tlet x = 1 &lt; 2;   \n\n    // #MAEK PARAGRAF \\# &amp; #KTHX\n}</code></pre>"
+    );
+    //no language means no class, and a block can be empty
+    assert_eq!(body("#HAI\n#MAEK CODE\n#KTHX CODE\n#KTHXBYE\n"), "<pre><code></code></pre>");
+}
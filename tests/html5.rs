@@ -0,0 +1,312 @@
+//! Checks that generated pages are well formed html5 documents.
+//!
+//! The checker is deliberately small, it only knows what the compiler can produce: a doctype up front, `<html lang>`,
+//! a head with the charset and exactly one title, a body, every non void element closed in the right order, no self closing syntax,
+//! and no block level elements inside a `<p>` (a browser would silently end the paragraph there).
+
+use lolcode_compiler::{compile_str, Options};
+
+const VOID_ELEMENTS: &[&str] = &["area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track", "wbr"];
+
+//elements whose start tag implicitly closes an open <p>
+const CLOSES_PARAGRAPH: &[&str] = &[
+    "address", "article", "aside", "blockquote", "details", "div", "dl", "fieldset", "figcaption", "figure", "footer", "form",
+    "h1", "h2", "h3", "h4", "h5", "h6", "header", "hgroup", "hr", "main", "menu", "nav", "ol", "p", "pre", "section", "table", "ul",
+];
+
+fn check_well_formed(html: &str) -> Result<(), String> {
+    let rest = html.strip_prefix("<!DOCTYPE html>").ok_or("missing <!DOCTYPE html> at the very start")?;
+    let mut open: Vec<String> = Vec::new();
+    let mut seen: Vec<String> = Vec::new();
+    let mut rest = rest;
+    while let Some(start) = rest.find('<') {
+        let text = &rest[..start];
+        if open.is_empty() && !text.trim().is_empty() {
+            return Err(format!("text outside of <html>: {:?}", text));
+        }
+        rest = &rest[start..];
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            let end = comment.find("-->").ok_or("unterminated comment")?;
+            rest = &comment[end + 3..];
+            continue;
+        }
+        let end = rest.find('>').ok_or("unterminated tag")?;
+        let tag = &rest[1..end];
+        rest = &rest[end + 1..];
+        if tag.ends_with('/') {
+            return Err(format!("self closing syntax on <{}>", tag));
+        }
+        if let Some(name) = tag.strip_prefix('/') {
+            match open.pop() {
+                Some(expected) if expected == name => {}
+                Some(expected) => return Err(format!("</{}> closes <{}>", name, expected)),
+                None => return Err(format!("</{}> without a matching start tag", name)),
+            }
+            continue;
+        }
+        let name = tag.split_whitespace().next().ok_or("empty tag")?.to_lowercase();
+        if CLOSES_PARAGRAPH.contains(&name.as_str()) && open.iter().any(|element| element == "p") {
+            return Err(format!("<{}> inside a <p>", name));
+        }
+        let expected_parent = match name.as_str() {
+            "html" => None,
+            "head" | "body" => Some("html"),
+            "meta" | "title" | "link" | "style" | "script" => Some("head"),
+            _ => Some("body"),
+        };
+        let parent = open.last().map(String::as_str);
+        let allowed = match expected_parent {
+            Some("body") => open.iter().any(|element| element == "body"),
+            other => parent == other,
+        };
+        if !allowed {
+            return Err(format!("<{}> inside {:?}", name, parent));
+        }
+        if name == "html" && !tag.contains("lang=\"") {
+            return Err("<html> without a lang attribute".to_string());
+        }
+        if name == "body" && !seen.iter().any(|element| element == "head") {
+            return Err("<body> before <head>".to_string());
+        }
+        if seen.last().is_some_and(|previous| previous == "head") && !(name == "meta" && tag.contains("charset=\"utf-8\"")) {
+            return Err("the charset has to be the first thing in <head>".to_string());
+        }
+        seen.push(name.clone());
+        if !VOID_ELEMENTS.contains(&name.as_str()) {
+            open.push(name);
+        }
+    }
+    if !rest.trim().is_empty() {
+        return Err(format!("text after </html>: {:?}", rest));
+    }
+    if !open.is_empty() {
+        return Err(format!("unclosed elements: {:?}", open));
+    }
+    for required in ["html", "head", "title", "body"] {
+        if seen.iter().filter(|element| *element == required).count() != 1 {
+            return Err(format!("expected exactly one <{}>", required));
+        }
+    }
+    Ok(())
+}
+
+//elements that start and end a line of their own, whitespace right inside or right around them doesnt show up on the page
+const BLOCK_ELEMENTS: &[&str] = &[
+    "html", "head", "body", "meta", "title", "link", "style", "script", "p", "ul", "ol", "li", "table", "thead", "tbody", "tr", "th", "td", "pre",
+    "h1", "h2", "h3", "h4", "h5", "h6", "section", "article", "header", "footer", "nav", "div", "blockquote", "hr",
+];
+
+//roughly what a browser shows for a page, as a list of tags and runs of text
+//runs of whitespace collapse to one space, whitespace next to a block element's tags is dropped, comments dont show up at all and <pre> keeps everything as is
+fn rendered(html: &str) -> Vec<String> {
+    enum Item {
+        Tag(String, bool),
+        //the text, and whether its inside a <pre>
+        Text(String, bool),
+    }
+    let mut items = Vec::new();
+    let mut text = String::new();
+    let mut in_pre = false;
+    let mut rest = html.strip_prefix("<!DOCTYPE html>").unwrap_or(html);
+    while !rest.is_empty() {
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = &comment[comment.find("-->").expect("unterminated comment") + 3..];
+        } else if rest.starts_with('<') {
+            let end = rest.find('>').expect("unterminated tag") + 1;
+            let tag = &rest[..end];
+            let name = tag.trim_matches(['<', '>', '/']).split_whitespace().next().unwrap_or("").to_lowercase();
+            items.push(Item::Text(std::mem::take(&mut text), in_pre));
+            if name == "pre" {
+                in_pre = !tag.starts_with("</");
+            }
+            items.push(Item::Tag(tag.to_string(), BLOCK_ELEMENTS.contains(&name.as_str())));
+            rest = &rest[end..];
+        } else {
+            let end = rest.find('<').unwrap_or(rest.len());
+            if in_pre {
+                text.push_str(&rest[..end]);
+            } else {
+                for c in rest[..end].chars() {
+                    if !c.is_whitespace() {
+                        text.push(c);
+                    } else if !text.ends_with(' ') {
+                        text.push(' ');
+                    }
+                }
+            }
+            rest = &rest[end..];
+        }
+    }
+    items.push(Item::Text(text, false));
+    let is_block = |item: Option<&Item>| !matches!(item, Some(Item::Tag(_, false)) | Some(Item::Text(..)));
+    let mut shown = Vec::new();
+    for (i, item) in items.iter().enumerate() {
+        match item {
+            Item::Tag(tag, _) => shown.push(tag.clone()),
+            Item::Text(text, preformatted) => {
+                let mut text = text.as_str();
+                if !preformatted && is_block(i.checked_sub(1).and_then(|previous| items.get(previous))) {
+                    text = text.trim_start();
+                }
+                if !preformatted && is_block(items.get(i + 1)) {
+                    text = text.trim_end();
+                }
+                if !text.is_empty() {
+                    shown.push(text.to_string());
+                }
+            }
+        }
+    }
+    shown
+}
+
+fn compile(source: &str, options: &Options) -> String {
+    compile_str(source, options).expect("document should compile").html
+}
+
+#[test]
+fn sample_documents_are_well_formed() {
+    for source in [include_str!("../src/test2.lol"), include_str!("../src/test3.lol")] {
+        for pretty in [false, true] {
+            let html = compile(source, &Options { pretty, ..Options::default() });
+            if let Err(problem) = check_well_formed(&html) {
+                panic!("{}\n{}", problem, html);
+            }
+        }
+    }
+}
+
+const EVERY_CONSTRUCT: &str = "#HAI
+#OBTW a comment #TLDR
+#MAEK HEAD #GIMMEH DESCRIPSHUN a \"test\" page #MKAY #GIMMEH TITLE caf\u{e9} #MKAY #GIMMEH AUTHOR Jon #MKAY #GIMMEH KEYWORDZ cats, lol #MKAY
+#GIMMEH VIEWPORT width=device-width, initial-scale=1 #MKAY #GIMMEH STYLESHEET site.css #MKAY #GIMMEH ICON favicon.ico #MKAY
+#MAEK STYLE
+body { color: #333; } p > b { margin: 0 }
+#KTHX STYLE
+#GIMMEH SCRIPT https://example.com/app.js #MKAY
+#OIC
+#I HAZ name #IT IZ Josh #MKAY
+#MAEK PARAGRAF
+#I HAZ inner #IT IZ Jon #MKAY
+before #LEMME SEE inner #MKAY #GIMMEH BOLD bold #GIMMEH ITALICS both #MKAY #MKAY #GIMMEH ITALICS italics #MKAY #GIMMEH NEWLINE
+#MAEK LIST #GIMMEH ITEM one #MKAY #GIMMEH ITEM #MKAY #OIC
+after the list #GIMMEH LINK https://example.com/?a=1&b=2 #IT SEZ a #GIMMEH BOLD link #MKAY #MKAY
+#GIMMEH SOUNDZ clips/theme.mp3 #MKAY
+#GIMMEH PIKCHUR cat.png #WIDF 200 #HITE 100 #IT SEZ a cat #MKAY #GIMMEH PIKCHUR line.png #IT SEZ #MKAY
+#GIMMEH VIDZ https://example.com/embed/1 #MKAY
+#OIC
+#MAEK PARAGRAF #MAEK LIST #GIMMEH ITEM only a list #MKAY #OIC #OIC
+#MAEK NUMBERZ #STARTIN AT 3 #MKAY #LOOKIN LIEK roman #MKAY #GIMMEH ITEM three #MKAY #OIC
+#MAEK LIST #GIMMEH ITEM outer #MAEK NUMBERZ #GIMMEH ITEM inner #MAEK LIST #GIMMEH ITEM deepest #MKAY #OIC #MKAY #OIC after #MKAY #OIC
+#MAEK PARAGRAF in a paragraph #MAEK TABLE #MAEK ROW #GIMMEH HEADER CELL a #MKAY #GIMMEH HEADER CELL b #MKAY #OIC #MAEK ROW #GIMMEH CELL 1 #MKAY #GIMMEH CELL #MAEK LIST #GIMMEH ITEM 2 #MKAY #OIC #MKAY #OIC #OIC #OIC
+#MAEK PARAGRAF #OIC
+#MAEK PARAGRAF code: #MAEK CODE html
+<p>a & b</p>   #GIMMEH BOLD
+#KTHX CODE #OIC
+#MAEK CODE
+ plain
+#KTHX CODE
+#GIMMEH HEADR 2 A #GIMMEH ITALICS heading #MKAY #MKAY
+#MAEK HEADER #GIMMEH HEADR 1 site #MKAY #MAEK NAV #MAEK LIST #GIMMEH ITEM #GIMMEH LINK /home #IT SEZ home #MKAY #MKAY #OIC #OIC #OIC
+#MAEK ARTICLE #MAEK SECTION #MAEK PARAGRAF in a section #OIC #GIMMEH RULE #MAEK DIV loose text #OIC #OIC
+#MAEK QUOTE #FROM https://example.com/source #MKAY #MAEK PARAGRAF quoted #OIC #MAEK LIST #GIMMEH ITEM listed #MKAY #OIC #OIC #OIC
+#MAEK FOOTER the end #OIC
+#MAEK PARAGRAF H#GIMMEH SUBSCRIPT 2 #MKAY O and x#GIMMEH SUPERSCRIPT 2 #MKAY #GIMMEH CODE a < b #MKAY #GIMMEH UNDERLINE u #GIMMEH STRIKETHRU s #GIMMEH HIGHLIGHT m #GIMMEH SMOL small #MKAY #MKAY #MKAY #MKAY #OIC
+#LEMME SEE name #MKAY
+#KTHXBYE
+";
+
+#[test]
+fn every_construct_is_well_formed() {
+    for pretty in [false, true] {
+        let html = compile(EVERY_CONSTRUCT, &Options { pretty, ..Options::default() });
+        if let Err(problem) = check_well_formed(&html) {
+            panic!("{}\n{}", problem, html);
+        }
+    }
+}
+
+#[test]
+fn pretty_output_renders_the_same() {
+    for source in [EVERY_CONSTRUCT, include_str!("../src/test2.lol"), include_str!("../src/test3.lol")] {
+        let plain = compile(source, &Options::default());
+        let pretty = compile(source, &Options { pretty: true, ..Options::default() });
+        assert_eq!(rendered(&plain), rendered(&pretty), "\n{}\n{}", plain, pretty);
+    }
+}
+
+#[test]
+fn pretty_output_is_indented() {
+    let source = "#HAI
+#MAEK HEAD #GIMMEH TITLE Cats #MKAY #GIMMEH STYLESHEET site.css #MKAY #OIC
+#MAEK LIST #GIMMEH ITEM one #MAEK NUMBERZ #GIMMEH ITEM two #MKAY #OIC #MKAY #GIMMEH ITEM three #MKAY #OIC
+#MAEK TABLE #MAEK ROW #GIMMEH HEADER CELL a #MKAY #OIC #MAEK ROW #GIMMEH CELL 1 #MKAY #OIC #OIC
+#MAEK PARAGRAF some #GIMMEH BOLD text #MKAY #GIMMEH SOUNDZ a.mp3 #MKAY #OIC
+#KTHXBYE
+";
+    let expected = r#"<!DOCTYPE html>
+<html lang="en">
+  <head>
+    <meta charset="utf-8">
+    <title>Cats </title>
+    <link rel="stylesheet" href="site.css">
+  </head>
+  <body>
+    <ul>
+      <li>one
+        <ol>
+          <li>two </li>
+        </ol>
+      </li>
+      <li>three </li>
+    </ul>
+    <table>
+      <thead>
+        <tr>
+          <th>a </th>
+        </tr>
+      </thead>
+      <tbody>
+        <tr>
+          <td>1 </td>
+        </tr>
+      </tbody>
+    </table>
+    <p>some <b>text </b><audio controls> <source src="a.mp3"></audio></p>
+  </body>
+</html>
+"#;
+    assert_eq!(compile(source, &Options { pretty: true, ..Options::default() }), expected);
+}
+
+#[test]
+fn rendering_notices_added_whitespace() {
+    assert_ne!(rendered("<p>see<iframe src=\"x\"></iframe><b>x</b></p>"), rendered("<p>see\n<iframe src=\"x\"></iframe>\n<b>x</b></p>"));
+    assert_eq!(rendered("<p>see  <b>x</b></p>\n<ul>\n  <li>a </li>\n</ul>"), rendered("<p>see <b>x</b></p><ul><li>a</li></ul>"));
+}
+
+#[test]
+fn document_without_a_head_is_titled_after_its_file() {
+    let html = compile("#HAI hi #KTHXBYE", &Options { file_name: "pages/cats.lol".to_string(), ..Options::default() });
+    assert!(html.starts_with("<!DOCTYPE html><html lang=\"en\"><head><meta charset=\"utf-8\"><title>cats</title></head><body>"), "{}", html);
+    check_well_formed(&html).unwrap();
+    let html = compile("#HAI hi #KTHXBYE", &Options::default());
+    assert!(html.contains("<title>&lt;input&gt;</title>"), "{}", html);
+    check_well_formed(&html).unwrap();
+}
+
+#[test]
+fn paragraph_only_holding_blocks_leaves_no_empty_paragraph() {
+    let html = compile("#HAI #MAEK PARAGRAF #MAEK LIST #GIMMEH ITEM a #MKAY #OIC #OIC #KTHXBYE", &Options::default());
+    assert!(html.ends_with("<body><ul><li>a </li></ul></body></html>"), "{}", html);
+    let html = compile("#HAI #MAEK PARAGRAF #OIC #MAEK PARAGRAF #I HAZ x #IT IZ y #MKAY #OIC #KTHXBYE", &Options::default());
+    assert!(html.ends_with("<body><p></p><p></p></body></html>"), "{}", html);
+}
+
+#[test]
+fn checker_rejects_what_the_compiler_used_to_produce() {
+    assert!(check_well_formed("<html><p>hi</p></html>").is_err());
+    assert!(check_well_formed("<!DOCTYPE html><html lang=\"en\"><head><meta charset=\"utf-8\"></head><body>hi</body></html>").is_err());
+    assert!(check_well_formed("<!DOCTYPE html><html lang=\"en\"><head><meta charset=\"utf-8\"></head><body><iframe src=\"x\"/></body></html>").is_err());
+    assert!(check_well_formed("<!DOCTYPE html><html lang=\"en\"><head><meta charset=\"utf-8\"></head><body><p><ul><li>x</li></ul></p></body></html>").is_err());
+}
@@ -0,0 +1,46 @@
+//! Regression tests for documents long enough to overflow the stack if the parser recursed once per element.
+//!
+//! Each compile runs on a thread with a small fixed stack, so recursing once per element would overflow after a few
+//! hundred of them. That means a hundred thousand elements is plenty to prove the stack use doesnt grow with the document,
+//! and it keeps the tests quick enough to run in a plain `cargo test`.
+
+use std::thread;
+
+use lolcode_compiler::{compile_str, Options};
+
+const ELEMENTS: usize = 100_000;
+const STACK_SIZE: usize = 256 * 1024;
+
+//compiles on a thread with only STACK_SIZE of stack, handing back the html
+fn compile_with_small_stack(source: String) -> String {
+    thread::Builder::new()
+        .stack_size(STACK_SIZE)
+        .spawn(move || compile_str(&source, &Options::default()).expect("document should compile").html)
+        .expect("thread should start")
+        .join()
+        .expect("compiling should not panic")
+}
+
+#[test]
+fn long_body() {
+    let mut source = String::from("#HAI\n");
+    for _ in 0..ELEMENTS / 2 {
+        source.push_str("cat #GIMMEH NEWLINE\n");
+    }
+    source.push_str("#KTHXBYE\n");
+
+    let html = compile_with_small_stack(source);
+    assert_eq!(html.matches("<br>").count(), ELEMENTS / 2);
+}
+
+#[test]
+fn long_list() {
+    let mut source = String::from("#HAI\n#MAEK PARAGRAF\n#MAEK LIST\n");
+    for _ in 0..ELEMENTS {
+        source.push_str("#GIMMEH ITEM cat #MKAY\n");
+    }
+    source.push_str("#OIC\n#OIC\n#KTHXBYE\n");
+
+    let html = compile_with_small_stack(source);
+    assert_eq!(html.matches("<li>").count(), ELEMENTS);
+}
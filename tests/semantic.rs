@@ -0,0 +1,236 @@
+//! Checks the html semantic analysis writes for text styles, images, lists, tables, sections and the head, and what it reports about them.
+
+use lolcode_compiler::{compile_str, Diagnostics, ErrorKind, Options, Output};
+
+fn compile(source: &str) -> Output {
+    compile_str(source, &Options::default()).expect("document should compile")
+}
+
+fn errors(source: &str) -> Diagnostics {
+    compile_str(source, &Options::default()).expect_err("document should not compile")
+}
+
+fn messages(diagnostics: &Diagnostics) -> Vec<&str> {
+    diagnostics.items.iter().map(|error| error.message.as_str()).collect()
+}
+
+#[test]
+fn image_attributes() {
+    let html = compile("#HAI #GIMMEH PIKCHUR cat.png #HITE 100 #WIDF 200 #IT SEZ a cat #MKAY #KTHXBYE").html;
+    assert!(html.contains("<img src=\"cat.png\" alt=\"a cat\" width=\"200\" height=\"100\">"), "{}", html);
+}
+
+#[test]
+fn decorative_image_gets_an_empty_alt() {
+    let html = compile("#HAI #GIMMEH PIKCHUR line.png #IT SEZ #MKAY #KTHXBYE").html;
+    assert!(html.contains("<img src=\"line.png\" alt=\"\">"), "{}", html);
+}
+
+#[test]
+fn image_without_alt_text_is_an_error() {
+    let diagnostics = errors("#HAI\n#GIMMEH PIKCHUR cat.png #WIDF 20 #MKAY\n#KTHXBYE");
+    assert_eq!(
+        messages(&diagnostics),
+        ["image is missing its alt text, describe it with #IT SEZ (or use an empty #IT SEZ #MKAY if its only decoration)"]
+    );
+    let error = &diagnostics.items[0];
+    assert_eq!(error.kind, ErrorKind::Semantic);
+    assert_eq!((error.location.line, error.location.column, error.location.len), (2, 1, "#GIMMEH PIKCHUR".len()));
+}
+
+#[test]
+fn bad_image_sizes_are_errors() {
+    let diagnostics = errors("#HAI #GIMMEH PIKCHUR a.png #WIDF 0 #HITE big #IT SEZ x #MKAY #GIMMEH PIKCHUR b.png #WIDF -3 #IT SEZ y #MKAY #KTHXBYE");
+    assert_eq!(
+        messages(&diagnostics),
+        [
+            "image size has to be a whole number of pixels, not '0'",
+            "image size has to be a whole number of pixels, not 'big'",
+            "image size has to be a whole number of pixels, not '-3'",
+        ]
+    );
+    assert!(diagnostics.items.iter().all(|error| error.kind == ErrorKind::Semantic));
+}
+
+#[test]
+fn text_styles_map_to_their_elements() {
+    let styled = |keyword: &str| compile(&format!("#HAI #MAEK PARAGRAF #GIMMEH {} x #MKAY #OIC #KTHXBYE", keyword)).html;
+    assert!(styled("BOLD").contains("<p><b>x </b></p>"));
+    assert!(styled("ITALICS").contains("<p><i>x </i></p>"));
+    assert!(styled("UNDERLINE").contains("<p><u>x </u></p>"));
+    assert!(styled("STRIKETHRU").contains("<p><s>x </s></p>"));
+    assert!(styled("CODE").contains("<p><code>x </code></p>"));
+    assert!(styled("SUBSCRIPT").contains("<p><sub>x </sub></p>"));
+    assert!(styled("SUPERSCRIPT").contains("<p><sup>x </sup></p>"));
+    assert!(styled("HIGHLIGHT").contains("<p><mark>x </mark></p>"));
+    assert!(styled("SMOL").contains("<p><small>x </small></p>"));
+}
+
+#[test]
+fn text_styles_work_in_list_items_and_cells() {
+    let html = compile("#HAI #MAEK LIST #GIMMEH ITEM H#GIMMEH SUBSCRIPT 2 #MKAY O #GIMMEH HIGHLIGHT #GIMMEH SMOL new #MKAY #MKAY #MKAY #OIC #KTHXBYE").html;
+    assert!(html.contains("<ul><li>H<sub>2 </sub>O <mark><small>new </small></mark></li></ul>"), "{}", html);
+    let html = compile("#HAI #MAEK TABLE #MAEK ROW #GIMMEH HEADER CELL #GIMMEH UNDERLINE a #MKAY #MKAY #GIMMEH CELL x#GIMMEH SUPERSCRIPT 2 #MKAY #GIMMEH STRIKETHRU old #MKAY #GIMMEH CODE f() #MKAY #MKAY #OIC #OIC #KTHXBYE").html;
+    assert!(html.contains("<tr><th><u>a </u></th><td>x<sup>2 </sup><s>old </s><code>f() </code></td></tr>"), "{}", html);
+}
+
+#[test]
+fn numbered_list_settings() {
+    let html = compile("#HAI #MAEK NUMBERZ #LOOKIN LIEK roman #MKAY #STARTIN AT 3 #MKAY #GIMMEH ITEM c #MKAY #OIC #KTHXBYE").html;
+    assert!(html.contains("<ol start=\"3\" type=\"i\"><li>c </li></ol>"), "{}", html);
+    for (style, kind) in [("numbers", "1"), ("1", "1"), ("letters", "a"), ("LETTERS", "A"), ("ROMAN", "I")] {
+        let html = compile(&format!("#HAI #MAEK NUMBERZ #LOOKIN LIEK {} #MKAY #GIMMEH ITEM x #MKAY #OIC #KTHXBYE", style)).html;
+        assert!(html.contains(&format!("<ol type=\"{}\">", kind)), "{}", html);
+    }
+    let html = compile("#HAI #MAEK NUMBERZ #STARTIN AT -2 #MKAY #GIMMEH ITEM x #MKAY #OIC #KTHXBYE").html;
+    assert!(html.contains("<ol start=\"-2\">"), "{}", html);
+}
+
+#[test]
+fn bad_list_settings_are_errors() {
+    let diagnostics = errors("#HAI\n#MAEK NUMBERZ #STARTIN AT three #MKAY #LOOKIN LIEK greek #MKAY #GIMMEH ITEM x #MKAY #OIC\n#KTHXBYE");
+    assert_eq!(
+        messages(&diagnostics),
+        [
+            "a list has to start at a whole number, not 'three'",
+            "unknown list style 'greek', expected one of numbers, letters, LETTERS, roman or ROMAN",
+        ]
+    );
+    assert!(diagnostics.items.iter().all(|error| error.kind == ErrorKind::Semantic));
+    assert_eq!((diagnostics.items[0].location.line, diagnostics.items[0].location.column), (2, 27));
+
+    let diagnostics = errors("#HAI\n#MAEK LIST #STARTIN AT 3 #MKAY #GIMMEH ITEM x #MKAY #OIC\n#KTHXBYE");
+    assert_eq!(messages(&diagnostics), ["only numbered lists (#MAEK NUMBERZ) can have #STARTIN AT"]);
+    assert_eq!(diagnostics.items[0].kind, ErrorKind::Syntax);
+    assert_eq!((diagnostics.items[0].location.line, diagnostics.items[0].location.column), (2, 12));
+
+    let diagnostics = errors("#HAI\n#MAEK NUMBERZ #LOOKIN LIEK roman #MKAY #LOOKIN LIEK letters #MKAY #GIMMEH ITEM x #MKAY #OIC\n#KTHXBYE");
+    assert_eq!(messages(&diagnostics), ["#LOOKIN LIEK given more than once for the same list"]);
+    assert_eq!(diagnostics.items[0].kind, ErrorKind::Syntax);
+    assert_eq!((diagnostics.items[0].location.line, diagnostics.items[0].location.column), (2, 40));
+}
+
+#[test]
+fn header_rows_at_the_top_go_in_the_thead() {
+    let source = "#HAI #MAEK TABLE
+#MAEK ROW #GIMMEH HEADER CELL a #MKAY #GIMMEH HEADER CELL b #MKAY #OIC
+#MAEK ROW #GIMMEH HEADER CELL 1 #MKAY #GIMMEH CELL 2 #MKAY #OIC
+#MAEK ROW #GIMMEH HEADER CELL 3 #MKAY #GIMMEH HEADER CELL 4 #MKAY #OIC
+#OIC #KTHXBYE";
+    let html = compile(source).html;
+    assert!(
+        html.contains(concat!(
+            "<table><thead><tr><th>a </th><th>b </th></tr></thead>",
+            "<tbody><tr><th>1 </th><td>2 </td></tr><tr><th>3 </th><th>4 </th></tr></tbody></table>",
+        )),
+        "{}",
+        html
+    );
+}
+
+#[test]
+fn table_without_header_rows_has_no_thead() {
+    let html = compile("#HAI #MAEK TABLE #MAEK ROW #GIMMEH CELL 1 #MKAY #OIC #OIC #KTHXBYE").html;
+    assert!(html.contains("<table><tbody><tr><td>1 </td></tr></tbody></table>"), "{}", html);
+}
+
+#[test]
+fn row_with_a_different_cell_count_is_only_a_warning() {
+    let source = "#HAI #MAEK TABLE
+#MAEK ROW #GIMMEH CELL a #MKAY #GIMMEH CELL b #MKAY #OIC
+#MAEK ROW #GIMMEH CELL 1 #MKAY #OIC
+#MAEK ROW #GIMMEH CELL 1 #MKAY #GIMMEH CELL 2 #MKAY #GIMMEH CELL 3 #MKAY #OIC
+#OIC #KTHXBYE";
+    let output = compile(source);
+    assert!(output.html.contains("<tr><td>1 </td></tr>"), "{}", output.html);
+    assert_eq!(
+        messages(&output.warnings),
+        ["row has 1 cell but the first row of the table has 2", "row has 3 cells but the first row of the table has 2"]
+    );
+    assert!(output.warnings.items.iter().all(|warning| warning.is_warning() && warning.kind == ErrorKind::Semantic));
+    assert_eq!(output.warnings.items[0].location.line, 3);
+    assert!(output.warnings.to_string().ends_with("warning: 2 warnings emitted\n"), "{}", output.warnings);
+}
+
+#[test]
+fn warnings_come_along_with_errors() {
+    let diagnostics = errors("#HAI #MAEK TABLE #MAEK ROW #GIMMEH CELL a #MKAY #GIMMEH CELL b #MKAY #OIC #MAEK ROW #OIC #OIC #LEMME SEE nope #MKAY #KTHXBYE");
+    assert_eq!(diagnostics.items.len(), 2);
+    assert!(diagnostics.items[0].is_warning());
+    assert!(!diagnostics.items[1].is_warning());
+    assert!(diagnostics.to_string().ends_with("error: aborting due to 1 previous error; 1 warning emitted\n"), "{}", diagnostics);
+}
+
+#[test]
+fn sections_quotes_and_rules() {
+    let source = "#HAI #MAEK QUOTE #FROM https://example.com/a?b=1&c=2 #MKAY said #OIC #GIMMEH RULE
+#MAEK ARTICLE #MAEK HEADER top #OIC #MAEK SECTION #MAEK PARAGRAF p #OIC #OIC #MAEK NAV n #OIC #MAEK DIV x #OIC #MAEK FOOTER bottom #OIC #OIC #KTHXBYE";
+    let html = compile(source).html;
+    assert!(
+        html.contains(concat!(
+            "<body><blockquote cite=\"https://example.com/a?b=1&amp;c=2\">said </blockquote><hr>",
+            "<article><header>top </header><section><p>p </p></section><nav>n </nav><div>x </div><footer>bottom </footer></article></body>",
+        )),
+        "{}",
+        html
+    );
+    let html = compile("#HAI #MAEK QUOTE unsourced #OIC #KTHXBYE").html;
+    assert!(html.contains("<body><blockquote>unsourced </blockquote></body>"), "{}", html);
+}
+
+#[test]
+fn quote_cite_goes_through_the_url_policy() {
+    let diagnostics = errors("#HAI #MAEK QUOTE #FROM javascript:x #MKAY said #OIC #KTHXBYE");
+    assert_eq!(messages(&diagnostics), ["url scheme 'javascript' is not allowed here"]);
+    assert_eq!(diagnostics.items[0].location.column, 24);
+}
+
+#[test]
+fn head_items_come_out_in_order() {
+    let source = "#HAI #MAEK HEAD
+#GIMMEH AUTHOR Jon #MKAY #GIMMEH TITLE page #MKAY #GIMMEH KEYWORDZ cats, lol #MKAY #GIMMEH VIEWPORT width=device-width #MKAY
+#GIMMEH DESCRIPSHUN about cats #MKAY #GIMMEH STYLESHEET site.css #MKAY #GIMMEH SCRIPT app.js #MKAY #GIMMEH ICON cat.ico #MKAY
+#MAEK STYLE
+p { color: #333; }
+#KTHX STYLE
+#OIC hi #KTHXBYE";
+    let html = compile(source).html;
+    assert!(
+        html.contains(concat!(
+            "<head><meta charset=\"utf-8\"><meta name=\"author\" content=\"Jon\"><title>page </title>",
+            "<meta name=\"keywords\" content=\"cats, lol\"><meta name=\"viewport\" content=\"width=device-width\">",
+            "<meta name=\"description\" content=\"about cats\"><link rel=\"stylesheet\" href=\"site.css\">",
+            "<script src=\"app.js\"></script><link rel=\"icon\" href=\"cat.ico\"><style>p { color: #333; }</style></head>",
+        )),
+        "{}",
+        html
+    );
+}
+
+#[test]
+fn second_title_is_an_error() {
+    let diagnostics = errors("#HAI #MAEK HEAD\n#GIMMEH TITLE one #MKAY\n#GIMMEH TITLE two #MKAY\n#OIC #KTHXBYE");
+    assert_eq!(messages(&diagnostics), ["the page already has a title, only one #GIMMEH TITLE is allowed"]);
+    let error = &diagnostics.items[0];
+    assert_eq!(error.kind, ErrorKind::Semantic);
+    assert_eq!((error.location.line, error.location.column), (3, 15));
+}
+
+#[test]
+fn head_without_a_title_is_an_error() {
+    let diagnostics = errors("#HAI #MAEK HEAD #GIMMEH AUTHOR Jon #MKAY #OIC #KTHXBYE");
+    assert_eq!(messages(&diagnostics), ["expected #GIMMEH TITLE but found #OIC instead"]);
+}
+
+#[test]
+fn style_block_cant_end_itself() {
+    let diagnostics = errors("#HAI #MAEK HEAD #GIMMEH TITLE t #MKAY #MAEK STYLE\np {}\n</STYLE><script>alert(1)</script>\n#KTHX STYLE\n#OIC #KTHXBYE");
+    assert_eq!(messages(&diagnostics), ["a style block cant have </style> in it, that would end it early"]);
+    assert_eq!(diagnostics.items[0].location.line, 2);
+}
+
+#[test]
+fn head_urls_are_checked() {
+    let diagnostics = errors("#HAI #MAEK HEAD #GIMMEH TITLE t #MKAY #GIMMEH SCRIPT javascript:alert(1) #MKAY #GIMMEH ICON a b.ico #MKAY #OIC #KTHXBYE");
+    assert_eq!(messages(&diagnostics), ["url scheme 'javascript' is not allowed here", "url 'a b.ico' contains whitespace"]);
+}